@@ -0,0 +1,56 @@
+//! Compares the time taken to step each engine on a 255x255 random soup.
+//!
+//! Run with `cargo bench`.
+
+use std::time::Instant;
+
+use game_of_life::{Grid, HashLife, PackedGrid, Rule, SparseGrid, Topology, Universe};
+
+const SIZE: u8 = 255;
+const GENERATIONS: u32 = 100;
+
+fn main() {
+    let cells = soup();
+    let as_i64 = || cells.iter().map(|&(x, y)| (x as i64, y as i64)).collect();
+
+    let grid = || Grid::new(SIZE, SIZE, cells.clone(), Rule::CONWAY, Topology::Torus);
+    let packed = || PackedGrid::new(SIZE, SIZE, cells.clone(), Rule::CONWAY, Topology::Torus);
+
+    let engines: Vec<(&str, Box<dyn Universe>)> = vec![
+        ("grid", Box::new(grid())),
+        ("grid, 4 threads", Box::new(grid().with_threads(4))),
+        ("packed", Box::new(packed())),
+        ("packed, 4 threads", Box::new(packed().with_threads(4))),
+        ("sparse", Box::new(SparseGrid::new(as_i64(), Rule::CONWAY))),
+        ("hashlife", Box::new(HashLife::new(as_i64(), Rule::CONWAY))),
+    ];
+
+    for (name, mut universe) in engines {
+        let start = Instant::now();
+        for _ in 0..GENERATIONS {
+            universe.step_forward();
+        }
+        let elapsed = start.elapsed();
+        println!(
+            "{name:>17}: {GENERATIONS} generations in {elapsed:>10.2?} ({:.2?} per generation)",
+            elapsed / GENERATIONS
+        );
+    }
+}
+
+/// Returns a deterministic soup with roughly a third of the cells alive.
+fn soup() -> Vec<(u8, u8)> {
+    let mut state: u32 = 0x2545_f491;
+    let mut cells = Vec::new();
+    for y in 0..SIZE {
+        for x in 0..SIZE {
+            state ^= state << 13;
+            state ^= state >> 17;
+            state ^= state << 5;
+            if state.is_multiple_of(3) {
+                cells.push((x, y));
+            }
+        }
+    }
+    cells
+}
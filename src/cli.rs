@@ -0,0 +1,749 @@
+use std::{
+    fmt::{self, Display, Write as _},
+    path::PathBuf,
+    str::FromStr,
+};
+
+use crate::{
+    config::{parse_number, parse_value, split_options},
+    Config, ConfigError, ConfigResult, PatternFormat, Rule, Search,
+};
+
+/// The name of the executable, used in help text and shell completions.
+const BIN: &str = env!("CARGO_PKG_NAME");
+const VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// An option shown in help text and shell completions, as `(name, value, description)`, where flags have no value.
+type OptionHelp = (&'static str, &'static str, &'static str);
+
+/// The subcommands and their descriptions.
+const COMMANDS: [(&str, &str); 6] = [
+    (
+        "run",
+        "Run a pattern in the terminal, headless or interactively",
+    ),
+    (
+        "analyze",
+        "Run a pattern until it settles and report how it behaves",
+    ),
+    ("convert", "Convert a pattern file to another format"),
+    (
+        "search",
+        "Run random soups looking for spaceships, oscillators and more",
+    ),
+    (
+        "completions",
+        "Print a shell completion script for bash, zsh or fish",
+    ),
+    ("help", "Print help for a command"),
+];
+
+const RUN_OPTIONS: &[OptionHelp] = &[
+    (
+        "--width",
+        "CELLS",
+        "Grid width, at most 255 for the grid and packed engines (default 40)",
+    ),
+    (
+        "--height",
+        "CELLS",
+        "Grid height, at most 255 for the grid and packed engines (default 20)",
+    ),
+    (
+        "--generations",
+        "N",
+        "Number of generations to run, or 0 to run forever (default 0)",
+    ),
+    (
+        "--cell",
+        "X,Y",
+        "A cell which starts alive, where 0,0 is the bottom-left cell (repeatable, signed with sparse or hashlife)",
+    ),
+    (
+        "--pattern",
+        "FILE",
+        "RLE, plaintext or Life 1.05/1.06 file placed in the centre of the grid",
+    ),
+    (
+        "--rule",
+        "RULE",
+        "Rule in B3/S23 or 23/3 notation (default Conway's rule)",
+    ),
+    (
+        "--delay",
+        "MS",
+        "Time between generations when drawing them (default 100)",
+    ),
+    (
+        "--topology",
+        "TOPOLOGY",
+        "bounded, horizontal, vertical or torus (default bounded)",
+    ),
+    (
+        "--engine",
+        "ENGINE",
+        "grid, packed, sparse or hashlife (default grid)",
+    ),
+    (
+        "--threads",
+        "N",
+        "Threads used to step grid and packed engines, or 0 for every core",
+    ),
+    (
+        "--random",
+        "DENSITY",
+        "Fill the grid with a random soup of the given density",
+    ),
+    (
+        "--seed",
+        "SEED",
+        "Seed of the random soup (default derived from the time)",
+    ),
+    (
+        "--region",
+        "X,Y,W,H",
+        "Area filled by the random soup (default the whole grid)",
+    ),
+    ("--save", "FILE", "Write the final state to a pattern file"),
+    (
+        "--headless",
+        "",
+        "Run without drawing, then print the final state and statistics",
+    ),
+    (
+        "--format",
+        "FORMAT",
+        "Format of the final state when headless: rle, cells, life105 or life106",
+    ),
+    (
+        "--interactive",
+        "",
+        "Run an interactive session which can be paused, stepped and reset",
+    ),
+    (
+        "--edit",
+        "",
+        "Start an interactive session in the pattern editor",
+    ),
+    (
+        "--stop-when-settled",
+        "",
+        "Stop once the pattern dies out or repeats",
+    ),
+    ("--zoom", "N", "Draw each N by N square of cells as one dot"),
+    ("--follow", "", "Keep the pattern centred in the view"),
+    (
+        "--glyphs",
+        "GLYPHS",
+        "text, halfblock or braille (default text)",
+    ),
+    (
+        "--color",
+        "MODE",
+        "Colour cells by age: off, 256 or truecolor (default off)",
+    ),
+    (
+        "--png",
+        "FILE",
+        "Write an image of the final state to a PNG file",
+    ),
+    (
+        "--gif",
+        "FILE",
+        "Record every generation into an animated GIF",
+    ),
+    (
+        "--cell-size",
+        "PIXELS",
+        "Size of each cell in exported images",
+    ),
+    (
+        "--alive-color",
+        "#RRGGBB",
+        "Colour of live cells in exported images (default black)",
+    ),
+    (
+        "--dead-color",
+        "#RRGGBB",
+        "Colour of dead cells in exported images (default white)",
+    ),
+    (
+        "--frame-delay",
+        "MS",
+        "Time each frame of a recorded GIF is shown for (default 100)",
+    ),
+    (
+        "--overlay",
+        "",
+        "Draw the generation number on each frame of a recorded GIF",
+    ),
+    (
+        "--svg",
+        "FILE",
+        "Write an image of the final state to an SVG file",
+    ),
+    (
+        "--svg-sheet",
+        "FILE",
+        "Write a sheet of the first generations to an SVG file",
+    ),
+    (
+        "--sheet-frames",
+        "N",
+        "Number of generations in the SVG sheet (default 8)",
+    ),
+    (
+        "--sheet-columns",
+        "N",
+        "Number of generations in each row of the SVG sheet",
+    ),
+    ("--gridlines", "", "Draw lines between cells in SVG images"),
+    ("--labels", "", "Label coordinates in SVG images"),
+    ("--age-colors", "", "Colour live cells by age in SVG images"),
+    (
+        "--stats",
+        "FILE",
+        "Write the statistics of every generation to a CSV or JSON lines file",
+    ),
+    (
+        "--stats-format",
+        "FORMAT",
+        "Format of the statistics file: csv or jsonl",
+    ),
+];
+
+const CONVERT_OPTIONS: &[OptionHelp] = &[(
+    "--format",
+    "FORMAT",
+    "rle, cells, life105 or life106 (default chosen by the output extension)",
+)];
+
+const SEARCH_OPTIONS: &[OptionHelp] = &[
+    (
+        "--find",
+        "TARGET",
+        "any, extinct, still-life, oscillator, spaceship or unsettled (default spaceship)",
+    ),
+    ("--trials", "N", "Number of soups to try (default 100)"),
+    (
+        "--seed",
+        "SEED",
+        "Seed of the first soup, after which seeds count upwards (default 0)",
+    ),
+    (
+        "--width",
+        "CELLS",
+        "Width of the area filled by each soup (default 16)",
+    ),
+    (
+        "--height",
+        "CELLS",
+        "Height of the area filled by each soup (default 16)",
+    ),
+    (
+        "--density",
+        "DENSITY",
+        "Probability of each cell in a soup being alive (default 0.35)",
+    ),
+    (
+        "--generations",
+        "N",
+        "Most generations each soup is run for (default 1000)",
+    ),
+    (
+        "--rule",
+        "RULE",
+        "Rule in B3/S23 or 23/3 notation (default Conway's rule)",
+    ),
+];
+
+/// A shell which completion scripts can be generated for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Shell {
+    Bash,
+    Zsh,
+    Fish,
+}
+
+impl FromStr for Shell {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "bash" => Ok(Shell::Bash),
+            "zsh" => Ok(Shell::Zsh),
+            "fish" => Ok(Shell::Fish),
+            _ => Err(s.to_string()),
+        }
+    }
+}
+
+impl Display for Shell {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Shell::Bash => "bash",
+            Shell::Zsh => "zsh",
+            Shell::Fish => "fish",
+        };
+        write!(f, "{name}")
+    }
+}
+
+/// What the command-line interface has been asked to do.
+#[derive(Clone)]
+pub enum Command {
+    /// Runs a pattern, drawing each generation unless headless.
+    Run(Config),
+    /// Runs a pattern until it settles or its cycle count is reached, then reports how it behaves.
+    Analyze(Config),
+    /// Converts a pattern file to another format, chosen by the output's extension unless `format` is given.
+    Convert {
+        input: PathBuf,
+        /// The file to write, or `-` for standard output.
+        output: PathBuf,
+        format: Option<PatternFormat>,
+    },
+    /// Searches random soups for ones with a chosen outcome.
+    Search(Search),
+    /// Prints a shell completion script.
+    Completions(Shell),
+    /// Prints some help text.
+    Help(String),
+    /// Prints the name and version of the program.
+    Version,
+}
+
+impl Command {
+    /// Parses the command-line arguments, without the program name.
+    ///
+    /// Arguments which do not start with a subcommand are parsed by [Config::build] as for `run`, so the original
+    /// `WIDTH HEIGHT GENERATIONS X,Y...` form still works.
+    ///
+    /// # Example
+    /// ```
+    /// use game_of_life::Command;
+    ///
+    /// let args = vec!["run", "--width", "10", "--height", "10", "--cell", "4,4", "--rule", "B36/S23"];
+    /// match Command::parse(args.into_iter().map(String::from).collect()).unwrap() {
+    ///     Command::Run(config) => assert_eq!(config.get_rule().to_string(), "B36/S23"),
+    ///     _ => unreachable!(),
+    /// }
+    ///
+    /// let args = vec!["search", "--help"];
+    /// let help = Command::parse(args.into_iter().map(String::from).collect()).unwrap();
+    /// assert!(matches!(help, Command::Help(text) if text.contains("--trials <N>")));
+    /// ```
+    pub fn parse(args: Vec<String>) -> ConfigResult<Self> {
+        let Some(first) = args.first() else {
+            return Ok(Command::Help(help(None)));
+        };
+        if matches!(first.as_str(), "--version" | "-V") {
+            return Ok(Command::Version);
+        }
+
+        let subcommand = COMMANDS
+            .iter()
+            .map(|&(name, _)| name)
+            .find(|&name| name == first);
+        let rest = match subcommand {
+            Some(_) => args[1..].to_vec(),
+            None => args.clone(),
+        };
+        if rest.iter().any(|arg| arg == "--help" || arg == "-h") {
+            return Ok(Command::Help(help(subcommand)));
+        }
+
+        let command = match subcommand {
+            Some("help") => match rest.first() {
+                Some(name) => match COMMANDS.iter().find(|&&(command, _)| command == name) {
+                    Some(&(command, _)) => Ok(Command::Help(help(Some(command)))),
+                    None => Err(ConfigError::UnknownCommand(name.clone())),
+                },
+                None => Ok(Command::Help(help(None))),
+            },
+            Some("analyze") => Config::build(rest).map(Command::Analyze),
+            Some("convert") => parse_convert(rest),
+            Some("search") => parse_search(rest),
+            Some("completions") => match rest.first() {
+                Some(shell) => Ok(Command::Completions(
+                    shell.parse().map_err(ConfigError::UnknownShell)?,
+                )),
+                None => Err(ConfigError::MissingArgument(String::from("SHELL"))),
+            },
+            // The only other subcommand is run
+            Some(_) => Config::build(rest).map(Command::Run),
+            None if first.starts_with('-') || first.starts_with(|c: char| c.is_ascii_digit()) => {
+                Config::build(rest).map(Command::Run)
+            }
+            None => Err(ConfigError::UnknownCommand(first.clone())),
+        };
+        // Positions in errors count from the argument after the subcommand
+        command.map_err(|err| err.offset_arguments(subcommand.map_or(0, |_| 1)))
+    }
+}
+
+fn parse_convert(args: Vec<String>) -> ConfigResult<Command> {
+    let (options, positional) = split_options(args)?;
+    let mut format = None;
+    for (option, value) in options {
+        match option.as_str() {
+            "--format" => format = Some(value.text.parse()?),
+            _ => return Err(ConfigError::UnknownOption(option)),
+        }
+    }
+
+    let mut positional = positional
+        .into_iter()
+        .map(|argument| PathBuf::from(argument.text));
+    let input = positional
+        .next()
+        .ok_or_else(|| ConfigError::MissingArgument(String::from("INPUT")))?;
+    let output = positional
+        .next()
+        .ok_or_else(|| ConfigError::MissingArgument(String::from("OUTPUT")))?;
+    if let Some(extra) = positional.next() {
+        return Err(ConfigError::UnexpectedArgument(
+            extra.to_string_lossy().into_owned(),
+        ));
+    }
+
+    Ok(Command::Convert {
+        input,
+        output,
+        format,
+    })
+}
+
+fn parse_search(args: Vec<String>) -> ConfigResult<Command> {
+    let (options, positional) = split_options(args)?;
+    if let Some(extra) = positional.into_iter().next() {
+        return Err(ConfigError::UnexpectedArgument(extra.text));
+    }
+
+    let mut search = Search::new();
+    let (mut width, mut height) = (16, 16);
+    for (option, value) in options {
+        search = match option.as_str() {
+            "--find" => search.with_target(
+                value
+                    .text
+                    .parse()
+                    .map_err(ConfigError::UnknownSearchTarget)?,
+            ),
+            "--trials" => search.with_trials(parse_number(&value)?),
+            "--seed" => search.with_seed(parse_number(&value)?),
+            "--width" => {
+                width = parse_number(&value)?;
+                search
+            }
+            "--height" => {
+                height = parse_number(&value)?;
+                search
+            }
+            "--density" => {
+                let density: f64 = parse_value(value.text.clone(), ConfigError::InvalidDensity)?;
+                if !(0.0..=1.0).contains(&density) {
+                    return Err(ConfigError::InvalidDensity(value.text));
+                }
+                search.with_density(density)
+            }
+            "--generations" => search.with_generations(parse_number(&value)?),
+            "--rule" => {
+                let rule: Rule = value.text.parse()?;
+                if rule.is_born(0) {
+                    return Err(ConfigError::UnboundedBirthOnZero);
+                }
+                search.with_rule(rule)
+            }
+            _ => return Err(ConfigError::UnknownOption(option)),
+        };
+    }
+
+    Ok(Command::Search(search.with_size(width, height)))
+}
+
+/// Returns the help text for a subcommand, or for the program if `subcommand` is `None`.
+pub fn help(subcommand: Option<&str>) -> String {
+    let mut text = String::new();
+    let (usage, description, options) = match subcommand {
+        Some("run") | Some("analyze") => (
+            "[OPTIONS] [WIDTH HEIGHT GENERATIONS [X,Y]...]",
+            if subcommand == Some("run") {
+                "Runs a pattern in the terminal, headless or interactively. The positional arguments are a shorter \
+                 form of --width, --height, --generations and --cell."
+            } else {
+                "Runs a pattern until it dies out, becomes a still life, oscillator or spaceship, or reaches the \
+                 number of generations (default 1000), then reports how it behaves."
+            },
+            RUN_OPTIONS,
+        ),
+        Some("convert") => (
+            "[OPTIONS] INPUT OUTPUT",
+            "Converts a pattern file to another format. OUTPUT may be - to print to standard output.",
+            CONVERT_OPTIONS,
+        ),
+        Some("search") => (
+            "[OPTIONS]",
+            "Runs random soups on an unbounded plane until they settle, printing those with the chosen outcome.",
+            SEARCH_OPTIONS,
+        ),
+        Some("completions") => (
+            "SHELL",
+            "Prints a completion script for bash, zsh or fish.",
+            &[][..],
+        ),
+        Some(_) | None => {
+            let _ = writeln!(text, "Conway's Game of Life and other life-like cellular automata.\n");
+            let _ = writeln!(text, "Usage: {BIN} [COMMAND] [OPTIONS]\n");
+            let _ = writeln!(text, "Commands:");
+            for (name, description) in COMMANDS {
+                let _ = writeln!(text, "  {name:<13}{description}");
+            }
+            let _ = writeln!(
+                text,
+                "\nWithout a command, the arguments are those of run.\n\nOptions:\n  -h, --help     Print help\n  \
+                 -V, --version  Print version\n\nExit codes:\n  1  The simulation or a file failed\n  2  An argument \
+                 could not be parsed, or is unknown or missing\n  3  The arguments describe an invalid configuration, \
+                 such as a cell outside the grid\n  4  A pattern file could not be read"
+            );
+            return text;
+        }
+    };
+
+    let subcommand = subcommand.unwrap_or_default();
+    let _ = writeln!(text, "{description}\n");
+    let _ = writeln!(text, "Usage: {BIN} {subcommand} {usage}\n");
+    let _ = writeln!(text, "Options:");
+    let width = options
+        .iter()
+        .map(|(name, value, _)| option_usage(name, value).len())
+        .max()
+        .unwrap_or_default()
+        .max(10);
+    for (name, value, description) in options {
+        let _ = writeln!(
+            text,
+            "  {:<width$}  {description}",
+            option_usage(name, value)
+        );
+    }
+    let _ = writeln!(text, "  {:<width$}  Print help", "-h, --help");
+    text
+}
+
+fn option_usage(name: &str, value: &str) -> String {
+    if value.is_empty() {
+        name.to_string()
+    } else {
+        format!("{name} <{value}>")
+    }
+}
+
+/// Returns the options of a subcommand, for shell completions.
+fn command_options(command: &str) -> &'static [OptionHelp] {
+    match command {
+        "run" | "analyze" => RUN_OPTIONS,
+        "convert" => CONVERT_OPTIONS,
+        "search" => SEARCH_OPTIONS,
+        _ => &[],
+    }
+}
+
+/// Returns a script which completes the subcommands and options of the program in `shell`.
+///
+/// # Example
+/// ```
+/// use game_of_life::{completions, Shell};
+///
+/// let script = completions(Shell::Bash);
+/// assert!(script.contains("complete -o default -F _game_of_life game_of_life"));
+/// assert!(completions(Shell::Fish).contains("-l generations"));
+/// ```
+pub fn completions(shell: Shell) -> String {
+    let mut script = String::new();
+    let names: Vec<&str> = COMMANDS.iter().map(|&(name, _)| name).collect();
+    match shell {
+        Shell::Bash => {
+            let _ = writeln!(script, "_{BIN}() {{");
+            let _ = writeln!(script, "    local cur=\"${{COMP_WORDS[COMP_CWORD]}}\" opts");
+            let _ = writeln!(script, "    if [ \"$COMP_CWORD\" -eq 1 ]; then");
+            let _ = writeln!(
+                script,
+                "        COMPREPLY=($(compgen -W \"{} --help --version\" -- \"$cur\"))",
+                names.join(" ")
+            );
+            let _ = writeln!(script, "        return");
+            let _ = writeln!(script, "    fi");
+            let _ = writeln!(script, "    case \"${{COMP_WORDS[1]}}\" in");
+            for name in &names {
+                let words: Vec<&str> = match *name {
+                    "completions" => vec!["bash", "zsh", "fish"],
+                    "help" => names.clone(),
+                    _ => command_options(name)
+                        .iter()
+                        .map(|(option, _, _)| *option)
+                        .collect(),
+                };
+                let _ = writeln!(
+                    script,
+                    "        {name}) opts=\"{} --help\" ;;",
+                    words.join(" ")
+                );
+            }
+            let _ = writeln!(script, "        *) opts=\"\" ;;");
+            let _ = writeln!(script, "    esac");
+            let _ = writeln!(
+                script,
+                "    COMPREPLY=($(compgen -W \"$opts\" -- \"$cur\"))"
+            );
+            let _ = writeln!(script, "}}");
+            let _ = writeln!(script, "complete -o default -F _{BIN} {BIN}");
+        }
+        Shell::Zsh => {
+            let _ = writeln!(script, "#compdef {BIN}\n");
+            let _ = writeln!(script, "_{BIN}() {{");
+            let _ = writeln!(script, "    local -a commands");
+            let _ = writeln!(script, "    commands=(");
+            for (name, description) in COMMANDS {
+                let _ = writeln!(script, "        '{name}:{}'", zsh_escape(description));
+            }
+            let _ = writeln!(script, "    )");
+            let _ = writeln!(script, "    if (( CURRENT == 2 )); then");
+            let _ = writeln!(script, "        _describe 'command' commands");
+            let _ = writeln!(script, "        return");
+            let _ = writeln!(script, "    fi");
+            let _ = writeln!(script, "    case $words[2] in");
+            for name in &names {
+                let _ = write!(script, "        {name}) _arguments");
+                for (option, value, description) in command_options(name) {
+                    let action = match *value {
+                        "" => String::new(),
+                        "FILE" => String::from(":file:_files"),
+                        value => format!(":{value}:"),
+                    };
+                    let _ = write!(script, " '{option}[{}]{action}'", zsh_escape(description));
+                }
+                match *name {
+                    "completions" => {
+                        let _ = write!(script, " '1:shell:(bash zsh fish)'");
+                    }
+                    "convert" => {
+                        let _ = write!(script, " '*:file:_files'");
+                    }
+                    _ => {}
+                }
+                let _ = writeln!(script, " ;;");
+            }
+            let _ = writeln!(script, "    esac");
+            let _ = writeln!(script, "}}\n");
+            let _ = writeln!(script, "_{BIN} \"$@\"");
+        }
+        Shell::Fish => {
+            let _ = writeln!(script, "complete -c {BIN} -f");
+            for (name, description) in COMMANDS {
+                let _ = writeln!(
+                    script,
+                    "complete -c {BIN} -n '__fish_use_subcommand' -a {name} -d '{}'",
+                    fish_escape(description)
+                );
+            }
+            for name in &names {
+                for (option, value, description) in command_options(name) {
+                    let argument = match *value {
+                        "" => "",
+                        "FILE" => " -r -F",
+                        _ => " -r",
+                    };
+                    let _ = writeln!(
+                        script,
+                        "complete -c {BIN} -n '__fish_seen_subcommand_from {name}' -l {}{argument} -d '{}'",
+                        option.trim_start_matches("--"),
+                        fish_escape(description)
+                    );
+                }
+            }
+            let _ = writeln!(
+                script,
+                "complete -c {BIN} -n '__fish_seen_subcommand_from completions' -a 'bash zsh fish'"
+            );
+            let _ = writeln!(
+                script,
+                "complete -c {BIN} -n '__fish_seen_subcommand_from convert' -F"
+            );
+        }
+    }
+    script
+}
+
+/// Escapes a description for use inside single quotes and square brackets in a zsh completion script.
+fn zsh_escape(text: &str) -> String {
+    text.replace('\'', "'\\''")
+        .replace('[', "\\[")
+        .replace(']', "\\]")
+        .replace(':', "\\:")
+}
+
+/// Escapes a description for use inside single quotes in a fish completion script.
+fn fish_escape(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('\'', "\\'")
+}
+
+/// Returns the name and version of the program.
+pub fn version() -> String {
+    format!("{BIN} {VERSION}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> ConfigResult<Command> {
+        Command::parse(args.iter().map(|&arg| String::from(arg)).collect())
+    }
+
+    fn argument_index(error: ConfigError) -> usize {
+        match error {
+            ConfigError::ArgsParsingError { argument, .. }
+            | ConfigError::StartingPointsParsingError { argument, .. }
+            | ConfigError::CellOutOfBounds {
+                argument: Some(argument),
+                ..
+            } => argument.index,
+            error => panic!("{error:?} has no argument"),
+        }
+    }
+
+    #[test]
+    fn positions_count_the_subcommand() {
+        for (args, index) in [
+            (&["10", "10", "0", "50,50"][..], 4),
+            (&["run", "10", "10", "0", "50,50"], 5),
+            (&["analyze", "10", "10", "0", "5x5"], 5),
+            (&["10", "abc", "0"], 2),
+            (&["run", "10", "abc", "0"], 3),
+            (&["search", "--trials", "abc"], 3),
+        ] {
+            assert_eq!(
+                argument_index(parse(args).err().unwrap()),
+                index,
+                "{args:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn errors_keep_their_exit_codes() {
+        assert_eq!(parse(&["bogus"]).err().unwrap().exit_code(), 2);
+        assert_eq!(
+            parse(&["run", "0", "10", "0"]).err().unwrap().exit_code(),
+            3
+        );
+        assert_eq!(
+            parse(&["run", "--pattern", "/does/not/exist.rle"])
+                .err()
+                .unwrap()
+                .exit_code(),
+            4
+        );
+    }
+}
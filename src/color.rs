@@ -0,0 +1,125 @@
+use std::{
+    fmt::{self, Display},
+    str::FromStr,
+};
+
+use crate::{CellAge, Region, Universe};
+
+const RESET: &str = "\x1b[0m";
+/// The colour of a cell which was just born.
+const BIRTH: (u8, u8, u8) = (255, 255, 255);
+/// The colour of a cell which just died.
+const DEATH: (u8, u8, u8) = (220, 40, 40);
+/// The colour of a live cell whose age is unknown.
+const ALIVE: (u8, u8, u8) = (220, 220, 220);
+/// The colours of live cells which have just been born and which have been alive for [OLD_AGE] generations or more.
+const YOUNG: (u8, u8, u8) = (255, 210, 0);
+const OLD: (u8, u8, u8) = (40, 90, 255);
+const OLD_AGE: u32 = 64;
+
+/// How cells are coloured when drawn in a terminal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ColorMode {
+    /// No colours.
+    #[default]
+    Off,
+    /// The 256 colours supported by most terminals.
+    Ansi256,
+    /// 24-bit colours.
+    TrueColor,
+}
+
+impl ColorMode {
+    /// Returns the escape sequence which sets the foreground colour, or an empty string if colours are off.
+    fn foreground(&self, (red, green, blue): (u8, u8, u8)) -> String {
+        match self {
+            ColorMode::Off => String::new(),
+            ColorMode::Ansi256 => {
+                let level = |value: u8| (value as u16 * 5 + 127) / 255;
+                let index = 16 + 36 * level(red) + 6 * level(green) + level(blue);
+                format!("\x1b[38;5;{index}m")
+            }
+            ColorMode::TrueColor => format!("\x1b[38;2;{red};{green};{blue}m"),
+        }
+    }
+}
+
+impl FromStr for ColorMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "off" | "none" => Ok(ColorMode::Off),
+            "256" | "ansi256" => Ok(ColorMode::Ansi256),
+            "truecolor" | "24bit" => Ok(ColorMode::TrueColor),
+            _ => Err(s.to_string()),
+        }
+    }
+}
+
+impl Display for ColorMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            ColorMode::Off => "off",
+            ColorMode::Ansi256 => "256",
+            ColorMode::TrueColor => "truecolor",
+        };
+        write!(f, "{name}")
+    }
+}
+
+/// Returns the colour of a live cell of the given age, fading from yellow when young to blue when old.
+pub fn age_color(age: u32) -> (u8, u8, u8) {
+    let t = age.min(OLD_AGE) as f64 / OLD_AGE as f64;
+    let mix = |young: u8, old: u8| (young as f64 + (old as f64 - young as f64) * t).round() as u8;
+    (
+        mix(YOUNG.0, OLD.0),
+        mix(YOUNG.1, OLD.1),
+        mix(YOUNG.2, OLD.2),
+    )
+}
+
+/// Returns the rows of a region of a universe like [frame_lines](crate::frame_lines), with live cells coloured by
+/// age, births in white and cells which just died drawn as a red `x`.
+///
+/// Universes which do not track the ages of their cells are drawn with every live cell in the same colour.
+///
+/// # Example
+/// ```
+/// use game_of_life::{colored_frame_lines, ColorMode, Grid, Region, Rule, Topology};
+///
+/// let mut grid = Grid::new(3, 3, vec![(0, 1), (1, 1), (2, 1)], Rule::CONWAY, Topology::Bounded);
+/// grid.step_forward();
+///
+/// let region = Region { x: 0, y: 0, width: 3, height: 3 };
+/// let lines = colored_frame_lines(&grid, region, ColorMode::Ansi256);
+/// assert_eq!(lines[1], "\x1b[38;5;167mx\x1b[0m \x1b[38;5;220mO\x1b[0m \x1b[38;5;167mx\x1b[0m");
+/// assert_eq!(lines[0], ". \x1b[38;5;231mO\x1b[0m .");
+/// ```
+pub fn colored_frame_lines<U: Universe + ?Sized>(
+    universe: &U,
+    region: Region,
+    mode: ColorMode,
+) -> Vec<String> {
+    (region.y..region.y + region.height)
+        .rev()
+        .map(|y| {
+            (region.x..region.x + region.width)
+                .map(|x| {
+                    let (symbol, color) = match universe.cell_age(x, y) {
+                        Some(CellAge::Alive(0)) => ("O", BIRTH),
+                        Some(CellAge::Alive(age)) => ("O", age_color(age)),
+                        Some(CellAge::Dead(0)) => ("x", DEATH),
+                        None if universe.is_alive(x, y) => ("O", ALIVE),
+                        _ => return ".".to_string(),
+                    };
+                    match mode {
+                        ColorMode::Off => symbol.to_string(),
+                        _ => format!("{}{symbol}{RESET}", mode.foreground(color)),
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join(" ")
+        })
+        .collect()
+}
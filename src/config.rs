@@ -0,0 +1,1565 @@
+use std::{fmt::Display, num::ParseIntError, path::PathBuf, str::FromStr};
+
+use crate::{
+    random_soup, Color, ColorMode, CoordinateError, Engine, Glyphs, HashLife, ImageStyle, Pattern,
+    PatternError, PatternFormat, Random, Region, Rule, RuleError, StatsFormat, SvgStyle, Topology,
+    Viewport,
+};
+
+pub type ConfigResult<T> = std::result::Result<T, ConfigError>;
+
+/// A command-line argument and its position among the arguments, counting from `1` as shells do.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Argument {
+    pub index: usize,
+    pub text: String,
+}
+
+#[derive(Debug, Clone)]
+pub enum ConfigError {
+    ArgsParsingError {
+        argument: Argument,
+        source: ParseIntError,
+    },
+    StartingSizeMissing,
+    CycleCountMissing,
+    StartingPointsParsingError {
+        argument: Argument,
+        /// Why the argument is not a cell, e.g. `expected x,y`.
+        reason: String,
+    },
+    /// A starting cell outside a fixed-size grid, with the argument it was given in, if any.
+    CellOutOfBounds {
+        error: CoordinateError,
+        argument: Option<Argument>,
+    },
+    ZeroSizeGrid {
+        width: u16,
+        height: u16,
+    },
+    /// A pattern file larger than a fixed-size grid.
+    PatternTooLarge {
+        width: i64,
+        height: i64,
+        grid_width: u16,
+        grid_height: u16,
+    },
+    /// A random soup region which is not entirely inside a fixed-size grid.
+    RegionOutsideGrid {
+        region: Region,
+        width: u16,
+        height: u16,
+    },
+    /// A random soup region covering too many cells to generate.
+    RegionTooLarge(Region),
+    RuleParsingError(RuleError),
+    MissingOptionValue(String),
+    UnknownOption(String),
+    UnknownTopology(String),
+    UnknownEngine(String),
+    UnknownGlyphs(String),
+    UnknownColorMode(String),
+    InvalidColor(String),
+    UnknownStatsFormat(String),
+    UnknownCommand(String),
+    UnknownShell(String),
+    UnknownSearchTarget(String),
+    MissingArgument(String),
+    UnexpectedArgument(String),
+    GridTooLarge(Engine),
+    UnboundedBirthOnZero,
+    /// A generation count too large for the hashlife engine to represent how far cells could move.
+    TooManyGenerations(usize),
+    PatternError(PatternError),
+    InvalidDensity(String),
+    InvalidRegion(String),
+    InvalidZoom(String),
+    InvalidCellSize(u32),
+    /// A PNG or GIF export larger than [ImageStyle::MAX_IMAGE_SIZE] or [ImageStyle::MAX_PIXELS].
+    ImageTooLarge {
+        width: u64,
+        height: u64,
+    },
+    /// An SVG sheet with no frames, or no frames in each row.
+    EmptySheet,
+    HeadlessWithoutCycleCount,
+    ConflictingOptions(String, String),
+}
+
+impl std::error::Error for ConfigError {}
+
+impl ConfigError {
+    /// Returns the exit code for this class of error:
+    /// * `2` - An argument could not be parsed, or a command, option or argument is unknown or missing
+    /// * `3` - The arguments describe an invalid configuration, such as a cell outside the grid or conflicting options
+    /// * `4` - A pattern file could not be read
+    ///
+    /// # Example
+    /// ```
+    /// use game_of_life::{Config, ConfigError};
+    ///
+    /// let args = vec!["10", "10", "0", "5,5", "50,50"];
+    /// let error = Config::build(args.into_iter().map(String::from).collect()).err().unwrap();
+    /// assert!(matches!(
+    ///     &error,
+    ///     ConfigError::CellOutOfBounds { argument: Some(argument), .. } if argument.index == 5
+    /// ));
+    /// assert_eq!(error.exit_code(), 3);
+    /// ```
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            ConfigError::CellOutOfBounds { .. }
+            | ConfigError::ZeroSizeGrid { .. }
+            | ConfigError::PatternTooLarge { .. }
+            | ConfigError::RegionOutsideGrid { .. }
+            | ConfigError::RegionTooLarge(_)
+            | ConfigError::GridTooLarge(_)
+            | ConfigError::UnboundedBirthOnZero
+            | ConfigError::TooManyGenerations(_)
+            | ConfigError::InvalidCellSize(_)
+            | ConfigError::ImageTooLarge { .. }
+            | ConfigError::EmptySheet
+            | ConfigError::HeadlessWithoutCycleCount
+            | ConfigError::ConflictingOptions(_, _) => 3,
+            ConfigError::PatternError(_) => 4,
+            _ => 2,
+        }
+    }
+
+    /// Moves the positions of any arguments in the error along by `offset`, for arguments which followed others.
+    pub(crate) fn offset_arguments(mut self, offset: usize) -> Self {
+        match &mut self {
+            ConfigError::ArgsParsingError { argument, .. }
+            | ConfigError::StartingPointsParsingError { argument, .. }
+            | ConfigError::CellOutOfBounds {
+                argument: Some(argument),
+                ..
+            } => argument.index += offset,
+            _ => {}
+        }
+        self
+    }
+}
+
+impl From<RuleError> for ConfigError {
+    fn from(err: RuleError) -> Self {
+        ConfigError::RuleParsingError(err)
+    }
+}
+
+impl From<PatternError> for ConfigError {
+    fn from(err: PatternError) -> Self {
+        ConfigError::PatternError(err)
+    }
+}
+
+impl Display for ConfigError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConfigError::ArgsParsingError { argument, source } => write!(
+                f,
+                "Could not parse \"{}\" (argument {}): {source}.",
+                argument.text, argument.index
+            ),
+            ConfigError::StartingSizeMissing => write!(f, "No starting size provided."),
+            ConfigError::CycleCountMissing => write!(f, "No cycle count provided."),
+            ConfigError::StartingPointsParsingError { argument, reason } => write!(
+                f,
+                "Could not parse starting point \"{}\" (argument {}): {reason}.",
+                argument.text, argument.index
+            ),
+            ConfigError::CellOutOfBounds {
+                error,
+                argument: Some(argument),
+            } => write!(
+                f,
+                "Starting point \"{}\" (argument {}) is outside the {}x{} grid.",
+                argument.text, argument.index, error.bounds.width, error.bounds.height
+            ),
+            ConfigError::CellOutOfBounds {
+                error,
+                argument: None,
+            } => write!(f, "{error}"),
+            ConfigError::ZeroSizeGrid { width, height } => write!(
+                f,
+                "The grid must be at least 1x1 cells, but is {width}x{height}."
+            ),
+            ConfigError::PatternTooLarge {
+                width,
+                height,
+                grid_width,
+                grid_height,
+            } => write!(
+                f,
+                "The {width}x{height} pattern does not fit in the {grid_width}x{grid_height} grid (use a larger grid, \
+                 or the sparse or hashlife engine)."
+            ),
+            ConfigError::RegionOutsideGrid {
+                region,
+                width,
+                height,
+            } => write!(
+                f,
+                "The region {},{},{},{} is not inside the {width}x{height} grid.",
+                region.x, region.y, region.width, region.height
+            ),
+            ConfigError::RegionTooLarge(region) => write!(
+                f,
+                "The region {},{},{},{} covers more than {MAX_REGION_AREA} cells.",
+                region.x, region.y, region.width, region.height
+            ),
+            ConfigError::RuleParsingError(rule_error) => {
+                write!(f, "Could not parse rule: {rule_error}")
+            }
+            ConfigError::MissingOptionValue(option) => {
+                write!(f, "No value provided for option \"{option}\".")
+            }
+            ConfigError::UnknownOption(option) => write!(f, "Unknown option \"{option}\"."),
+            ConfigError::UnknownTopology(topology) => write!(
+                f,
+                "Unknown topology \"{topology}\" (expected bounded, horizontal, vertical or torus)."
+            ),
+            ConfigError::UnknownEngine(engine) => {
+                write!(
+                    f,
+                    "Unknown engine \"{engine}\" (expected grid, packed, sparse or hashlife)."
+                )
+            }
+            ConfigError::UnknownGlyphs(glyphs) => write!(
+                f,
+                "Unknown glyphs \"{glyphs}\" (expected text, halfblock or braille)."
+            ),
+            ConfigError::UnknownColorMode(colors) => write!(
+                f,
+                "Unknown color mode \"{colors}\" (expected off, 256 or truecolor)."
+            ),
+            ConfigError::InvalidColor(color) => {
+                write!(f, "Invalid color \"{color}\" (expected #rrggbb).")
+            }
+            ConfigError::UnknownCommand(command) => write!(
+                f,
+                "Unknown command \"{command}\" (expected run, analyze, convert, search, completions or help)."
+            ),
+            ConfigError::UnknownShell(shell) => {
+                write!(f, "Unknown shell \"{shell}\" (expected bash, zsh or fish).")
+            }
+            ConfigError::UnknownSearchTarget(target) => write!(
+                f,
+                "Unknown search target \"{target}\" (expected any, extinct, still-life, oscillator, spaceship or \
+                 unsettled)."
+            ),
+            ConfigError::MissingArgument(argument) => write!(f, "No {argument} provided."),
+            ConfigError::UnexpectedArgument(argument) => {
+                write!(f, "Unexpected argument \"{argument}\".")
+            }
+            ConfigError::UnknownStatsFormat(format) => write!(
+                f,
+                "Unknown statistics format \"{format}\" (expected csv or jsonl)."
+            ),
+            ConfigError::GridTooLarge(engine) => write!(
+                f,
+                "The {engine} engine supports grids of at most 255x255 cells (use the sparse or hashlife engine for \
+                 larger grids)."
+            ),
+            ConfigError::UnboundedBirthOnZero => write!(
+                f,
+                "Rules with birth on zero neighbours cannot be used with an unbounded engine."
+            ),
+            ConfigError::TooManyGenerations(generations) => write!(
+                f,
+                "The hashlife engine can run at most {} generations, not {generations}.",
+                HashLife::MAX_GENERATIONS
+            ),
+            ConfigError::PatternError(pattern_error) => {
+                write!(f, "Could not read pattern: {pattern_error}")
+            }
+            ConfigError::InvalidDensity(density) => write!(
+                f,
+                "Invalid density \"{density}\" (expected a number between 0 and 1)."
+            ),
+            ConfigError::InvalidZoom(zoom) => write!(
+                f,
+                "Invalid zoom \"{zoom}\" (expected a number of cells per dot between 1 and {}).",
+                Viewport::MAX_SCALE
+            ),
+            ConfigError::InvalidCellSize(cell_size) => write!(
+                f,
+                "Invalid cell size {cell_size} (expected a number of pixels between 1 and {}).",
+                ImageStyle::MAX_CELL_SIZE
+            ),
+            ConfigError::ImageTooLarge { width, height } => write!(
+                f,
+                "A {width}x{height} pixel image is too large to export (use a smaller grid or cell size)."
+            ),
+            ConfigError::EmptySheet => write!(
+                f,
+                "The SVG sheet must have at least one frame and one column."
+            ),
+            ConfigError::InvalidRegion(region) => write!(
+                f,
+                "Invalid region \"{region}\" (expected x,y,width,height)."
+            ),
+            ConfigError::HeadlessWithoutCycleCount => {
+                write!(
+                    f,
+                    "Headless mode requires a cycle count greater than 0 or --stop-when-settled."
+                )
+            }
+            ConfigError::ConflictingOptions(first, second) => {
+                write!(
+                    f,
+                    "Options \"{first}\" and \"{second}\" cannot be used together."
+                )
+            }
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct Config {
+    grid_width: u16,
+    grid_height: u16,
+    /// The number of cycles to complete, or `0` to run indefinitely
+    cycle_count: usize,
+    /// The time between generations when drawing them, in milliseconds
+    delay: u64,
+    /// A vector of coordinates of cells which should start in an alive state
+    starting_cells: Vec<(i64, i64)>,
+    /// The birth/survival rule used to evolve the grid
+    rule: Rule,
+    /// How the edges of the grid connect to each other
+    topology: Topology,
+    /// The backend used to store and evolve the game state
+    engine: Engine,
+    /// The number of threads used to step fixed-size grids, or `0` to use every available core
+    threads: usize,
+    /// A file to write the final game state to
+    save_path: Option<PathBuf>,
+    /// The seed used to generate a random soup, if one was requested
+    seed: Option<u64>,
+    /// Whether to run without drawing each generation, printing only the final state and statistics
+    headless: bool,
+    /// The format the final state is printed in when running headless
+    output_format: PatternFormat,
+    /// Whether to run an interactive session which can be paused, stepped and reset
+    interactive: bool,
+    /// Whether to start the interactive session in the pattern editor
+    edit: bool,
+    /// Whether to stop once the pattern has settled into a repeating cycle
+    stop_when_settled: bool,
+    /// The number of cells along each side of the square drawn by one dot
+    zoom: i64,
+    /// Whether to keep the pattern centred in the view
+    follow: bool,
+    /// How cells are drawn as characters
+    glyphs: Glyphs,
+    /// How cells are coloured by age
+    colors: ColorMode,
+    /// A PNG file to write the final game state to
+    png_path: Option<PathBuf>,
+    /// A GIF file to record every generation into
+    gif_path: Option<PathBuf>,
+    /// The cell size and colours of exported images
+    image_style: ImageStyle,
+    /// The time each frame of a recorded GIF is shown for, in milliseconds
+    frame_delay: u64,
+    /// An SVG file to write the final game state to
+    svg_path: Option<PathBuf>,
+    /// An SVG file to write a sheet of the first generations to
+    svg_sheet_path: Option<PathBuf>,
+    /// The appearance of SVG images
+    svg_style: SvgStyle,
+    /// The number of generations in the SVG sheet
+    sheet_frames: usize,
+    /// The number of generations in each row of the SVG sheet
+    sheet_columns: usize,
+    /// A file to write the statistics of every generation to
+    stats_path: Option<PathBuf>,
+    /// The format statistics are written in
+    stats_format: StatsFormat,
+}
+
+impl Config {
+    /// Builds and returns the [Config] object from command-line arguments, or a [ConfigError].
+    ///
+    /// The arguments are parsed into a [ConfigBuilder], which can also be used directly to build a config from typed
+    /// values.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - Options in any order, optionally with these positional arguments in place of the first options:
+    ///     * `0` - Grid width, as with `--width`
+    ///     * `1` - Grid height, as with `--height`
+    ///     * `2` - Cycle count, as with `--generations`
+    ///     * `3+` - Starting coordinates, as with `--cell`
+    ///
+    /// Options may appear anywhere in `args`:
+    /// * `--width <CELLS>` and `--height <CELLS>` - The size of the grid, of at most 255 for the `grid` and `packed`
+    ///   engines (default to 40 by 20, which fits a standard terminal)
+    /// * `--generations <N>` - The number of generations to run, or `0` (default) to run indefinitely
+    /// * `--cell <X,Y>` - A cell which starts alive, where `0,0` is the bottom-left cell, which may be repeated and may
+    ///   have negative or larger coordinates outside the grid with the `sparse` and `hashlife` engines
+    /// * `--delay <MS>` - The time between generations when drawing them (defaults to 100)
+    /// * `--rule <RULESTRING>` - The rule to use, in `B3/S23` or `23/3` notation (defaults to Conway's rule)
+    /// * `--topology <TOPOLOGY>` - One of `bounded` (default), `horizontal`, `vertical` or `torus`
+    /// * `--engine <ENGINE>` - Either `grid` (default) or `packed` for a fixed-size grid, or `sparse` or `hashlife` for
+    ///   an unbounded plane, in which case the grid size only determines the printed region and the topology is ignored
+    /// * `--threads <N>` - The number of threads used to step `grid` and `packed` engines, or `0` to use every core
+    /// * `--pattern <FILE>` - An RLE, plaintext or Life 1.05/1.06 file whose cells are placed in the centre of the
+    ///   grid, and whose rule is used unless `--rule` is also given
+    /// * `--save <FILE>` - A file to write the final game state to, in a format chosen by its extension
+    /// * `--random <DENSITY>` - Fills the grid with a random soup, where each cell is alive with probability `DENSITY`
+    /// * `--seed <SEED>` - The seed for the random soup, which defaults to one derived from the current time
+    /// * `--region <X,Y,WIDTH,HEIGHT>` - The area filled by the random soup, which defaults to the whole grid and can cover
+    ///   at most 16777216 cells
+    /// * `--headless` - Runs every cycle as fast as possible without drawing, then prints the final state and
+    ///   statistics (requires a cycle count or `--stop-when-settled`)
+    /// * `--format <FORMAT>` - The format of the final state printed when running headless, one of `rle` (default),
+    ///   `cells`, `life105` or `life106`
+    /// * `--interactive` - Runs an interactive session, where `space` pauses, `n` steps, `+`/`-` change the speed, `r`
+    ///   resets, `e` edits the cells and `q` quits, pausing once the cycle count is reached
+    /// * `--stop-when-settled` - Stops once the pattern dies out or becomes a still life, oscillator or spaceship, or
+    ///   pauses in an interactive session
+    /// * `--edit` - Starts an interactive session in the pattern editor
+    /// * `--zoom <N>` - Draws each square of `N` by `N` cells as one dot, where text dots are shaded by how many cells
+    ///   are alive
+    /// * `--follow` - Keeps the pattern's bounding box centred in the view
+    /// * `--glyphs <GLYPHS>` - One of `text` (default) for one cell per character, `halfblock` for two cells per
+    ///   character or `braille` for eight cells per character
+    /// * `--color <MODE>` - One of `off` (default), `256` or `truecolor`, colouring live cells by age and highlighting
+    ///   births and deaths when drawing text with the `grid` engine
+    /// * `--png <FILE>` - A PNG file to write an image of the final game state to
+    /// * `--gif <FILE>` - A GIF file to record an animation of every generation into
+    /// * `--cell-size <PIXELS>` - The width and height of each cell in exported images, from 1 to 256 (defaults to 4
+    ///   for PNG and GIF images and 10 for SVG images)
+    /// * `--alive-color <#RRGGBB>` and `--dead-color <#RRGGBB>` - The colours of cells in exported images (default to
+    ///   black on white)
+    /// * `--frame-delay <MS>` - The time each frame of a recorded GIF is shown for (defaults to 100)
+    /// * `--overlay` - Draws the generation number on each frame of a recorded GIF
+    /// * `--svg <FILE>` - An SVG file to write an image of the final game state to
+    /// * `--svg-sheet <FILE>` - An SVG file to write a sheet of the first generations side by side to
+    /// * `--sheet-frames <N>` - The number of generations in the SVG sheet (defaults to 8)
+    /// * `--sheet-columns <N>` - The number of generations in each row of the SVG sheet (defaults to all of them)
+    /// * `--gridlines`, `--labels` and `--age-colors` - Draws lines between cells, labels coordinates and colours live
+    ///   cells by age in SVG images
+    /// * `--stats <FILE>` - A file to write the population, births, deaths, bounding box and density of every
+    ///   generation to
+    /// * `--stats-format <FORMAT>` - Either `csv` or `jsonl` (JSON lines), which defaults to the one matching the
+    ///   extension of the statistics file, or `csv`
+    ///
+    /// # Example
+    /// ```
+    /// use game_of_life::Config;
+    ///
+    /// let args = vec!["10", "10", "0", "2,4", "2,5", "3,5", "--rule", "B36/S23"];
+    /// let config = Config::build(args.into_iter().map(String::from).collect()).unwrap();
+    /// assert_eq!(config.get_rule().to_string(), "B36/S23");
+    /// ```
+    pub fn build(args: Vec<String>) -> ConfigResult<Self> {
+        let (options, args) = split_options(args)?;
+
+        let mut builder = ConfigBuilder::new(DEFAULT_WIDTH, DEFAULT_HEIGHT);
+        let mut cell_arguments = Vec::new();
+        if !args.is_empty() {
+            if args.len() < 2 {
+                return Err(ConfigError::StartingSizeMissing);
+            } else if args.len() < 3 {
+                return Err(ConfigError::CycleCountMissing);
+            }
+            builder = builder
+                .with_width(parse_number(&args[0])?)
+                .with_height(parse_number(&args[1])?)
+                .with_cycles(parse_number(&args[2])?);
+            cell_arguments.extend_from_slice(&args[3..]);
+        }
+
+        let mut alive_color = Color::BLACK;
+        let mut dead_color = Color::WHITE;
+        let mut overlay = false;
+        let mut gridlines = false;
+        let mut labels = false;
+        let mut age_colors = false;
+
+        for (option, value) in options {
+            builder = match option.as_str() {
+                "--width" => builder.with_width(parse_number(&value)?),
+                "--height" => builder.with_height(parse_number(&value)?),
+                "--generations" => builder.with_cycles(parse_number(&value)?),
+                "--cell" => {
+                    cell_arguments.push(value);
+                    builder
+                }
+                "--delay" => builder.with_delay(parse_number(&value)?),
+                "--rule" => builder.with_rule(value.text.parse()?),
+                "--topology" => {
+                    builder.with_topology(value.text.parse().map_err(ConfigError::UnknownTopology)?)
+                }
+                "--engine" => {
+                    builder.with_engine(value.text.parse().map_err(ConfigError::UnknownEngine)?)
+                }
+                "--threads" => builder.with_threads(parse_number(&value)?),
+                "--pattern" => builder.with_pattern(PatternSource::File(PathBuf::from(value.text))),
+                "--save" => builder.with_save_path(value.text),
+                "--random" => {
+                    builder.with_random_soup(parse_value(value.text, ConfigError::InvalidDensity)?)
+                }
+                "--seed" => builder.with_seed(parse_number(&value)?),
+                "--region" => builder.with_region(parse_region(&value.text)?),
+                "--headless" => builder.with_headless(true),
+                "--interactive" => builder.with_interactive(true),
+                "--edit" => builder.with_edit(true),
+                "--stop-when-settled" => builder.with_stop_when_settled(true),
+                "--zoom" => builder.with_zoom(parse_value(value.text, ConfigError::InvalidZoom)?),
+                "--follow" => builder.with_follow(true),
+                "--glyphs" => {
+                    builder.with_glyphs(value.text.parse().map_err(ConfigError::UnknownGlyphs)?)
+                }
+                "--color" => {
+                    builder.with_colors(value.text.parse().map_err(ConfigError::UnknownColorMode)?)
+                }
+                "--png" => builder.with_png_path(value.text),
+                "--gif" => builder.with_gif_path(value.text),
+                "--frame-delay" => builder.with_frame_delay(parse_number(&value)?),
+                "--svg" => builder.with_svg_path(value.text),
+                "--svg-sheet" => builder.with_svg_sheet_path(value.text),
+                "--sheet-frames" => builder.with_sheet_frames(parse_number(&value)?),
+                "--sheet-columns" => builder.with_sheet_columns(parse_number(&value)?),
+                "--stats" => builder.with_stats_path(value.text),
+                "--stats-format" => builder.with_stats_format(
+                    value
+                        .text
+                        .parse()
+                        .map_err(ConfigError::UnknownStatsFormat)?,
+                ),
+                "--format" => builder.with_output_format(value.text.parse()?),
+                "--cell-size" => builder.with_cell_size(parse_number(&value)?),
+                "--alive-color" => {
+                    alive_color = value.text.parse().map_err(ConfigError::InvalidColor)?;
+                    builder
+                }
+                "--dead-color" => {
+                    dead_color = value.text.parse().map_err(ConfigError::InvalidColor)?;
+                    builder
+                }
+                "--overlay" => {
+                    overlay = true;
+                    builder
+                }
+                "--gridlines" => {
+                    gridlines = true;
+                    builder
+                }
+                "--labels" => {
+                    labels = true;
+                    builder
+                }
+                "--age-colors" => {
+                    age_colors = true;
+                    builder
+                }
+                _ => return Err(ConfigError::UnknownOption(option)),
+            };
+        }
+
+        let cells = cell_arguments
+            .iter()
+            .map(parse_cell)
+            .collect::<ConfigResult<Vec<_>>>()?;
+
+        builder
+            .with_cells(cells.clone())
+            .with_image_style(
+                ImageStyle::default()
+                    .with_colors(alive_color, dead_color)
+                    .with_overlay(overlay.then_some(Color::RED)),
+            )
+            .with_svg_style(
+                SvgStyle::default()
+                    .with_colors(alive_color, dead_color)
+                    .with_gridlines(gridlines.then_some(GRIDLINE_COLOR))
+                    .with_labels(labels)
+                    .with_age_colors(age_colors),
+            )
+            .build()
+            .map_err(|err| match err {
+                ConfigError::CellOutOfBounds {
+                    error,
+                    argument: None,
+                } => {
+                    let argument = cells
+                        .iter()
+                        .position(|&cell| cell == (error.x, error.y))
+                        .map(|index| cell_arguments[index].clone());
+                    ConfigError::CellOutOfBounds { error, argument }
+                }
+                err => err,
+            })
+    }
+
+    /// Replaces the cells which start alive.
+    pub(crate) fn set_starting_cells(&mut self, cells: Vec<(i64, i64)>) {
+        self.starting_cells = cells;
+    }
+
+    pub fn get_x(&self) -> u16 {
+        self.grid_width
+    }
+
+    pub fn get_y(&self) -> u16 {
+        self.grid_height
+    }
+
+    pub fn get_starting_cells(&self) -> Vec<(i64, i64)> {
+        self.starting_cells.clone()
+    }
+
+    pub fn get_cycle_count(&self) -> usize {
+        self.cycle_count
+    }
+
+    pub fn get_delay(&self) -> u64 {
+        self.delay
+    }
+
+    pub fn get_rule(&self) -> Rule {
+        self.rule
+    }
+
+    pub fn get_topology(&self) -> Topology {
+        self.topology
+    }
+
+    pub fn get_engine(&self) -> Engine {
+        self.engine
+    }
+
+    pub fn get_threads(&self) -> usize {
+        self.threads
+    }
+
+    pub fn get_save_path(&self) -> Option<PathBuf> {
+        self.save_path.clone()
+    }
+
+    pub fn get_seed(&self) -> Option<u64> {
+        self.seed
+    }
+
+    pub fn is_headless(&self) -> bool {
+        self.headless
+    }
+
+    pub fn get_output_format(&self) -> PatternFormat {
+        self.output_format
+    }
+
+    pub fn is_interactive(&self) -> bool {
+        self.interactive
+    }
+
+    pub fn is_editing(&self) -> bool {
+        self.edit
+    }
+
+    pub fn stops_when_settled(&self) -> bool {
+        self.stop_when_settled
+    }
+
+    pub fn get_zoom(&self) -> i64 {
+        self.zoom
+    }
+
+    pub fn is_following(&self) -> bool {
+        self.follow
+    }
+
+    pub fn get_glyphs(&self) -> Glyphs {
+        self.glyphs
+    }
+
+    pub fn get_colors(&self) -> ColorMode {
+        self.colors
+    }
+
+    pub fn get_png_path(&self) -> Option<PathBuf> {
+        self.png_path.clone()
+    }
+
+    pub fn get_gif_path(&self) -> Option<PathBuf> {
+        self.gif_path.clone()
+    }
+
+    pub fn get_image_style(&self) -> ImageStyle {
+        self.image_style
+    }
+
+    pub fn get_frame_delay(&self) -> u64 {
+        self.frame_delay
+    }
+
+    pub fn get_svg_path(&self) -> Option<PathBuf> {
+        self.svg_path.clone()
+    }
+
+    pub fn get_svg_sheet_path(&self) -> Option<PathBuf> {
+        self.svg_sheet_path.clone()
+    }
+
+    pub fn get_svg_style(&self) -> SvgStyle {
+        self.svg_style
+    }
+
+    pub fn get_sheet_frames(&self) -> usize {
+        self.sheet_frames
+    }
+
+    pub fn get_sheet_columns(&self) -> usize {
+        self.sheet_columns
+    }
+
+    pub fn get_stats_path(&self) -> Option<PathBuf> {
+        self.stats_path.clone()
+    }
+
+    pub fn get_stats_format(&self) -> StatsFormat {
+        self.stats_format
+    }
+}
+
+/// Where the starting pattern of a [Config] comes from.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PatternSource {
+    /// A pattern which has already been read.
+    Pattern(Pattern),
+    /// An RLE, plaintext or Life 1.05/1.06 file, which is read when the config is built.
+    File(PathBuf),
+}
+
+/// Builds a [Config] from typed values, validating them when [built](ConfigBuilder::build).
+///
+/// Every setting other than the grid size has the same default as when it is left out of the arguments to
+/// [Config::build].
+///
+/// # Example
+/// ```
+/// use game_of_life::{ConfigBuilder, ConfigError, Engine, Game, Rule, Topology};
+///
+/// let config = ConfigBuilder::new(20, 20)
+///     .with_cycles(10)
+///     .with_rule(Rule::CONWAY)
+///     .with_topology(Topology::Torus)
+///     .with_cells(vec![(1, 2), (2, 1), (0, 0), (1, 0), (2, 0)])
+///     .build()
+///     .unwrap();
+/// let mut game = Game::new(config);
+/// game.advance(4).unwrap();
+/// assert_eq!(game.population(), 5);
+///
+/// let error = ConfigBuilder::new(300, 300).with_random_soup(0.5).build().err();
+/// assert!(matches!(error, Some(ConfigError::GridTooLarge(Engine::Grid))));
+///
+/// let error = ConfigBuilder::new(0, 10).build().err();
+/// assert!(matches!(error, Some(ConfigError::ZeroSizeGrid { width: 0, height: 10 })));
+/// ```
+#[derive(Debug, Clone)]
+pub struct ConfigBuilder {
+    width: u16,
+    height: u16,
+    cycles: usize,
+    delay: u64,
+    cells: Vec<(i64, i64)>,
+    rule: Option<Rule>,
+    topology: Topology,
+    engine: Engine,
+    threads: usize,
+    pattern: Option<PatternSource>,
+    save_path: Option<PathBuf>,
+    density: Option<f64>,
+    seed: Option<u64>,
+    region: Option<Region>,
+    headless: bool,
+    output_format: PatternFormat,
+    interactive: bool,
+    edit: bool,
+    stop_when_settled: bool,
+    zoom: i64,
+    follow: bool,
+    glyphs: Glyphs,
+    colors: ColorMode,
+    png_path: Option<PathBuf>,
+    gif_path: Option<PathBuf>,
+    image_style: ImageStyle,
+    /// A cell size which replaces that of both image styles.
+    cell_size: Option<u32>,
+    frame_delay: u64,
+    svg_path: Option<PathBuf>,
+    svg_sheet_path: Option<PathBuf>,
+    svg_style: SvgStyle,
+    sheet_frames: usize,
+    sheet_columns: Option<usize>,
+    stats_path: Option<PathBuf>,
+    stats_format: Option<StatsFormat>,
+}
+
+impl ConfigBuilder {
+    /// Creates a builder for a grid of `width` by `height` cells, which is at most 255 by 255 for the `grid` and
+    /// `packed` engines.
+    pub fn new(width: u16, height: u16) -> Self {
+        Self {
+            width,
+            height,
+            cycles: 0,
+            delay: 100,
+            cells: Vec::new(),
+            rule: None,
+            topology: Topology::default(),
+            engine: Engine::default(),
+            threads: 1,
+            pattern: None,
+            save_path: None,
+            density: None,
+            seed: None,
+            region: None,
+            headless: false,
+            output_format: PatternFormat::Rle,
+            interactive: false,
+            edit: false,
+            stop_when_settled: false,
+            zoom: 1,
+            follow: false,
+            glyphs: Glyphs::default(),
+            colors: ColorMode::default(),
+            png_path: None,
+            gif_path: None,
+            image_style: ImageStyle::default(),
+            cell_size: None,
+            frame_delay: 100,
+            svg_path: None,
+            svg_sheet_path: None,
+            svg_style: SvgStyle::default(),
+            sheet_frames: 8,
+            sheet_columns: None,
+            stats_path: None,
+            stats_format: None,
+        }
+    }
+
+    /// Sets the number of cycles to complete, or `0` to run indefinitely.
+    pub fn with_cycles(mut self, cycles: usize) -> Self {
+        self.cycles = cycles;
+        self
+    }
+
+    pub fn with_width(mut self, width: u16) -> Self {
+        self.width = width;
+        self
+    }
+
+    pub fn with_height(mut self, height: u16) -> Self {
+        self.height = height;
+        self
+    }
+
+    /// Sets the time between generations when drawing them, in milliseconds.
+    pub fn with_delay(mut self, delay: u64) -> Self {
+        self.delay = delay;
+        self
+    }
+
+    /// Sets the cells which start alive, where `0,0` is the bottom-left cell, and which must be inside a fixed-size grid.
+    pub fn with_cells(mut self, cells: Vec<(i64, i64)>) -> Self {
+        self.cells = cells;
+        self
+    }
+
+    /// Sets the rule, which otherwise comes from the pattern or defaults to Conway's rule.
+    pub fn with_rule(mut self, rule: Rule) -> Self {
+        self.rule = Some(rule);
+        self
+    }
+
+    pub fn with_topology(mut self, topology: Topology) -> Self {
+        self.topology = topology;
+        self
+    }
+
+    pub fn with_engine(mut self, engine: Engine) -> Self {
+        self.engine = engine;
+        self
+    }
+
+    /// Sets the number of threads used to step fixed-size grids, or `0` to use every available core.
+    pub fn with_threads(mut self, threads: usize) -> Self {
+        self.threads = threads;
+        self
+    }
+
+    /// Places a pattern in the centre of the grid, whose rule is used unless another is set.
+    pub fn with_pattern(mut self, pattern: PatternSource) -> Self {
+        self.pattern = Some(pattern);
+        self
+    }
+
+    /// Sets a file to write the final game state to, in a format chosen by its extension.
+    pub fn with_save_path<P: Into<PathBuf>>(mut self, path: P) -> Self {
+        self.save_path = Some(path.into());
+        self
+    }
+
+    /// Fills the grid with a random soup, where each cell is alive with probability `density`.
+    pub fn with_random_soup(mut self, density: f64) -> Self {
+        self.density = Some(density);
+        self
+    }
+
+    /// Sets the seed of the random soup, which otherwise is derived from the current time.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// Sets the area filled by the random soup, which otherwise is the whole grid, and can cover at most 16777216 cells.
+    pub fn with_region(mut self, region: Region) -> Self {
+        self.region = Some(region);
+        self
+    }
+
+    /// Runs without drawing each generation, which requires a cycle count or stopping when settled.
+    pub fn with_headless(mut self, headless: bool) -> Self {
+        self.headless = headless;
+        self
+    }
+
+    /// Sets the format the final state is printed in when running headless.
+    pub fn with_output_format(mut self, format: PatternFormat) -> Self {
+        self.output_format = format;
+        self
+    }
+
+    pub fn with_interactive(mut self, interactive: bool) -> Self {
+        self.interactive = interactive;
+        self
+    }
+
+    /// Starts an interactive session in the pattern editor.
+    pub fn with_edit(mut self, edit: bool) -> Self {
+        self.edit = edit;
+        self
+    }
+
+    /// Stops once the pattern has settled into a repeating cycle.
+    pub fn with_stop_when_settled(mut self, stop_when_settled: bool) -> Self {
+        self.stop_when_settled = stop_when_settled;
+        self
+    }
+
+    /// Sets the number of cells along each side of the square drawn by one dot.
+    pub fn with_zoom(mut self, zoom: i64) -> Self {
+        self.zoom = zoom;
+        self
+    }
+
+    /// Keeps the pattern centred in the view.
+    pub fn with_follow(mut self, follow: bool) -> Self {
+        self.follow = follow;
+        self
+    }
+
+    pub fn with_glyphs(mut self, glyphs: Glyphs) -> Self {
+        self.glyphs = glyphs;
+        self
+    }
+
+    pub fn with_colors(mut self, colors: ColorMode) -> Self {
+        self.colors = colors;
+        self
+    }
+
+    /// Sets a PNG file to write the final game state to.
+    pub fn with_png_path<P: Into<PathBuf>>(mut self, path: P) -> Self {
+        self.png_path = Some(path.into());
+        self
+    }
+
+    /// Sets a GIF file to record every generation into.
+    pub fn with_gif_path<P: Into<PathBuf>>(mut self, path: P) -> Self {
+        self.gif_path = Some(path.into());
+        self
+    }
+
+    /// Sets the width and height of each cell in exported images, in pixels from 1 to
+    /// [ImageStyle::MAX_CELL_SIZE], in place of the cell size of the image and SVG styles.
+    pub fn with_cell_size(mut self, cell_size: u32) -> Self {
+        self.cell_size = Some(cell_size);
+        self
+    }
+
+    /// Sets the cell size and colours of PNG and GIF images.
+    pub fn with_image_style(mut self, style: ImageStyle) -> Self {
+        self.image_style = style;
+        self
+    }
+
+    /// Sets the time each frame of a recorded GIF is shown for, in milliseconds.
+    pub fn with_frame_delay(mut self, delay: u64) -> Self {
+        self.frame_delay = delay;
+        self
+    }
+
+    /// Sets an SVG file to write the final game state to.
+    pub fn with_svg_path<P: Into<PathBuf>>(mut self, path: P) -> Self {
+        self.svg_path = Some(path.into());
+        self
+    }
+
+    /// Sets an SVG file to write a sheet of the first generations to.
+    pub fn with_svg_sheet_path<P: Into<PathBuf>>(mut self, path: P) -> Self {
+        self.svg_sheet_path = Some(path.into());
+        self
+    }
+
+    pub fn with_svg_style(mut self, style: SvgStyle) -> Self {
+        self.svg_style = style;
+        self
+    }
+
+    /// Sets the number of generations in the SVG sheet, which must be at least 1.
+    pub fn with_sheet_frames(mut self, frames: usize) -> Self {
+        self.sheet_frames = frames;
+        self
+    }
+
+    /// Sets the number of generations in each row of the SVG sheet, which must be at least 1 and otherwise fits them all
+    /// in one row.
+    pub fn with_sheet_columns(mut self, columns: usize) -> Self {
+        self.sheet_columns = Some(columns);
+        self
+    }
+
+    /// Sets a file to write the statistics of every generation to.
+    pub fn with_stats_path<P: Into<PathBuf>>(mut self, path: P) -> Self {
+        self.stats_path = Some(path.into());
+        self
+    }
+
+    /// Sets the format of the statistics file, which otherwise is chosen by its extension.
+    pub fn with_stats_format(mut self, format: StatsFormat) -> Self {
+        self.stats_format = Some(format);
+        self
+    }
+
+    /// Validates the settings, reads the pattern file if there is one and returns the [Config], or a [ConfigError].
+    pub fn build(self) -> ConfigResult<Config> {
+        if self.headless && (self.interactive || self.edit) {
+            let other = if self.edit { "--edit" } else { "--interactive" };
+            return Err(ConfigError::ConflictingOptions(
+                String::from("--headless"),
+                String::from(other),
+            ));
+        }
+
+        let pattern = match self.pattern {
+            Some(PatternSource::Pattern(pattern)) => Some(pattern),
+            Some(PatternSource::File(path)) => Some(Pattern::load(path)?),
+            None => None,
+        };
+
+        let rule = self
+            .rule
+            .or(pattern.as_ref().and_then(Pattern::rule))
+            .unwrap_or_default();
+
+        if matches!(self.engine, Engine::Sparse | Engine::HashLife) && rule.is_born(0) {
+            return Err(ConfigError::UnboundedBirthOnZero);
+        }
+
+        let max_size = u8::MAX as u16;
+        if matches!(self.engine, Engine::Grid | Engine::Packed)
+            && (self.width > max_size || self.height > max_size)
+        {
+            return Err(ConfigError::GridTooLarge(self.engine));
+        }
+
+        if self.width == 0 || self.height == 0 {
+            return Err(ConfigError::ZeroSizeGrid {
+                width: self.width,
+                height: self.height,
+            });
+        }
+
+        if self.engine == Engine::HashLife && self.cycles as u64 > HashLife::MAX_GENERATIONS {
+            return Err(ConfigError::TooManyGenerations(self.cycles));
+        }
+
+        if self.headless && self.cycles == 0 && !self.stop_when_settled {
+            return Err(ConfigError::HeadlessWithoutCycleCount);
+        }
+
+        if let Some(density) = self
+            .density
+            .filter(|density| !(0.0..=1.0).contains(density))
+        {
+            return Err(ConfigError::InvalidDensity(density.to_string()));
+        }
+
+        if !(1..=Viewport::MAX_SCALE).contains(&self.zoom) {
+            return Err(ConfigError::InvalidZoom(self.zoom.to_string()));
+        }
+
+        if let Some(cell_size) = self
+            .cell_size
+            .filter(|cell_size| !(1..=ImageStyle::MAX_CELL_SIZE).contains(cell_size))
+        {
+            return Err(ConfigError::InvalidCellSize(cell_size));
+        }
+
+        // Raster images cover the whole grid and are held in memory, which limits their size even on unbounded engines
+        if self.png_path.is_some() || self.gif_path.is_some() {
+            let cell_size = self.cell_size.unwrap_or(self.image_style.cell_size()) as u64;
+            let width = self.width as u64 * cell_size;
+            let height = self.height as u64 * cell_size;
+            if !ImageStyle::fits(width, height) {
+                return Err(ConfigError::ImageTooLarge { width, height });
+            }
+        }
+
+        if self.sheet_frames == 0 || self.sheet_columns == Some(0) {
+            return Err(ConfigError::EmptySheet);
+        }
+
+        let invalid_region = |region: Region| {
+            ConfigError::InvalidRegion(format!(
+                "{},{},{},{}",
+                region.x, region.y, region.width, region.height
+            ))
+        };
+        if let Some(region) = self
+            .region
+            .filter(|region| region.width < 0 || region.height < 0)
+        {
+            return Err(invalid_region(region));
+        }
+
+        if let Some(region) = self.region.filter(|region| {
+            region
+                .width
+                .checked_mul(region.height)
+                .is_none_or(|area| area > MAX_REGION_AREA)
+        }) {
+            return Err(ConfigError::RegionTooLarge(region));
+        }
+
+        // The soup is generated from the region's bottom-left corner up to its top-right corner, which must exist
+        if let Some(region) = self.region.filter(|region| {
+            region.x.checked_add(region.width).is_none()
+                || region.y.checked_add(region.height).is_none()
+        }) {
+            return Err(invalid_region(region));
+        }
+
+        // Fixed-size grids cannot hold cells outside them, so every source of cells must fit
+        if matches!(self.engine, Engine::Grid | Engine::Packed) {
+            let bounds = Region {
+                x: 0,
+                y: 0,
+                width: self.width as i64,
+                height: self.height as i64,
+            };
+            for &(x, y) in &self.cells {
+                CoordinateError::check(x, y, bounds).map_err(|error| {
+                    ConfigError::CellOutOfBounds {
+                        error,
+                        argument: None,
+                    }
+                })?;
+            }
+
+            if let Some(pattern) = pattern.as_ref().filter(|pattern| {
+                pattern.width() > bounds.width || pattern.height() > bounds.height
+            }) {
+                return Err(ConfigError::PatternTooLarge {
+                    width: pattern.width(),
+                    height: pattern.height(),
+                    grid_width: self.width,
+                    grid_height: self.height,
+                });
+            }
+
+            let outside = |start: i64, size: i64, limit: i64| {
+                start < 0 || start.checked_add(size).is_none_or(|end| end > limit)
+            };
+            if let Some(region) = self.region.filter(|region| {
+                outside(region.x, region.width, bounds.width)
+                    || outside(region.y, region.height, bounds.height)
+            }) {
+                return Err(ConfigError::RegionOutsideGrid {
+                    region,
+                    width: self.width,
+                    height: self.height,
+                });
+            }
+        }
+
+        let mut starting_cells: Vec<(i64, i64)> = Vec::new();
+
+        let seed = self.density.map(|density| {
+            let seed = self.seed.unwrap_or_else(Random::seed_from_time);
+            let region = self.region.unwrap_or(Region {
+                x: 0,
+                y: 0,
+                width: self.width as i64,
+                height: self.height as i64,
+            });
+            starting_cells.extend(random_soup(seed, density, region));
+            seed
+        });
+
+        if let Some(pattern) = pattern {
+            let offset_x = (self.width as i64 - pattern.width()) / 2;
+            let offset_y = (self.height as i64 - pattern.height()) / 2;
+            starting_cells.extend(
+                pattern
+                    .cells()
+                    .iter()
+                    .map(|&(x, y)| (x + offset_x, y + offset_y)),
+            );
+        }
+
+        for point in self.cells {
+            if !starting_cells.contains(&point) {
+                starting_cells.push(point);
+            }
+        }
+
+        Ok(Config {
+            grid_width: self.width,
+            grid_height: self.height,
+            cycle_count: self.cycles,
+            delay: self.delay,
+            starting_cells,
+            rule,
+            topology: self.topology,
+            engine: self.engine,
+            threads: self.threads,
+            save_path: self.save_path,
+            seed,
+            headless: self.headless,
+            output_format: self.output_format,
+            interactive: self.interactive || self.edit,
+            edit: self.edit,
+            stop_when_settled: self.stop_when_settled,
+            zoom: self.zoom,
+            follow: self.follow,
+            glyphs: self.glyphs,
+            colors: self.colors,
+            png_path: self.png_path,
+            gif_path: self.gif_path,
+            image_style: match self.cell_size {
+                Some(cell_size) => self.image_style.with_cell_size(cell_size),
+                None => self.image_style,
+            },
+            frame_delay: self.frame_delay,
+            svg_path: self.svg_path,
+            svg_sheet_path: self.svg_sheet_path,
+            svg_style: match self.cell_size {
+                Some(cell_size) => self.svg_style.with_cell_size(cell_size),
+                None => self.svg_style,
+            },
+            sheet_frames: self.sheet_frames,
+            sheet_columns: self.sheet_columns.unwrap_or(self.sheet_frames),
+            stats_format: self
+                .stats_format
+                .or_else(|| self.stats_path.as_ref().map(StatsFormat::from_path))
+                .unwrap_or_default(),
+            stats_path: self.stats_path,
+        })
+    }
+}
+
+/// Parses a starting cell in the format `x,y`.
+fn parse_cell(argument: &Argument) -> ConfigResult<(i64, i64)> {
+    let error = |reason: String| ConfigError::StartingPointsParsingError {
+        argument: argument.clone(),
+        reason,
+    };
+    let (x, y) = argument
+        .text
+        .split_once(',')
+        .ok_or_else(|| error(String::from("expected x,y")))?;
+    let x = x
+        .parse()
+        .map_err(|err| error(format!("invalid x coordinate ({err})")))?;
+    let y = y
+        .parse()
+        .map_err(|err| error(format!("invalid y coordinate ({err})")))?;
+    Ok((x, y))
+}
+
+/// Parses a whole number, or returns a [ConfigError::ArgsParsingError] naming the argument if it is not one.
+pub(crate) fn parse_number<T: FromStr<Err = ParseIntError>>(
+    argument: &Argument,
+) -> ConfigResult<T> {
+    argument
+        .text
+        .parse()
+        .map_err(|source| ConfigError::ArgsParsingError {
+            argument: argument.clone(),
+            source,
+        })
+}
+
+/// Parses a number, or returns `error` with the text if it is not one.
+pub(crate) fn parse_value<T: FromStr>(
+    value: String,
+    error: fn(String) -> ConfigError,
+) -> ConfigResult<T> {
+    value.parse().map_err(|_| error(value))
+}
+
+/// Parses a region in the format `x,y,width,height`.
+fn parse_region(value: &str) -> ConfigResult<Region> {
+    let components: Vec<i64> = value
+        .split(',')
+        .map(str::parse)
+        .collect::<Result<_, _>>()
+        .map_err(|_| ConfigError::InvalidRegion(value.to_string()))?;
+
+    match components[..] {
+        [x, y, width, height] => Ok(Region {
+            x,
+            y,
+            width,
+            height,
+        }),
+        _ => Err(ConfigError::InvalidRegion(value.to_string())),
+    }
+}
+
+/// The most cells a random soup region can cover, which keeps generating the soup quick on unbounded engines.
+const MAX_REGION_AREA: i64 = 1 << 24;
+
+/// The size of the grid when none is given, which fits a standard 80 by 24 terminal.
+const DEFAULT_WIDTH: u16 = 40;
+const DEFAULT_HEIGHT: u16 = 20;
+
+/// The colour of lines drawn between cells in SVG images.
+const GRIDLINE_COLOR: Color = Color::new(200, 200, 200);
+
+/// A list of `(option, value)` pairs, e.g. `("--rule", "B3/S23")`, where each value keeps its position.
+pub(crate) type Options = Vec<(String, Argument)>;
+
+/// Options which take no value, and are given an empty one.
+const FLAGS: [&str; 9] = [
+    "--headless",
+    "--interactive",
+    "--edit",
+    "--stop-when-settled",
+    "--follow",
+    "--overlay",
+    "--gridlines",
+    "--labels",
+    "--age-colors",
+];
+
+/// Separates `--option value` pairs and flags from the positional arguments.
+pub(crate) fn split_options(args: Vec<String>) -> ConfigResult<(Options, Vec<Argument>)> {
+    let mut options = Vec::new();
+    let mut positional = Vec::new();
+    let mut args = args.into_iter().enumerate().map(|(index, text)| Argument {
+        index: index + 1,
+        text,
+    });
+
+    while let Some(Argument { index, text: arg }) = args.next() {
+        if !arg.starts_with("--") {
+            positional.push(Argument { index, text: arg });
+        } else if FLAGS.contains(&arg.as_str()) {
+            options.push((
+                arg,
+                Argument {
+                    index,
+                    text: String::new(),
+                },
+            ));
+        } else if let Some((option, value)) = arg.split_once('=') {
+            let value = Argument {
+                index,
+                text: value.to_string(),
+            };
+            options.push((option.to_string(), value));
+        } else {
+            match args.next() {
+                Some(value) => options.push((arg, value)),
+                None => return Err(ConfigError::MissingOptionValue(arg)),
+            }
+        }
+    }
+
+    Ok((options, positional))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn build(args: &[&str]) -> ConfigError {
+        Config::build(args.iter().map(|&arg| String::from(arg)).collect())
+            .err()
+            .unwrap()
+    }
+
+    #[test]
+    fn parse_errors_have_exit_code_2() {
+        for args in [
+            &["10", "abc", "0"][..],
+            &["10", "10", "0", "5x5"],
+            &["--unknown"],
+            &["--width"],
+            &["--rule", "B9/S23"],
+        ] {
+            assert_eq!(build(args).exit_code(), 2, "{args:?}");
+        }
+    }
+
+    #[test]
+    fn invalid_configurations_have_exit_code_3() {
+        for args in [
+            &["10", "10", "0", "50,50"][..],
+            &["0", "10", "0"],
+            &["300", "10", "0"],
+            &["--headless"],
+            &["--headless", "--edit", "--generations", "1"],
+            &["10", "10", "0", "--random", "0.5", "--region", "5,5,20,20"],
+            &["--random", "0.5", "--region", "1,0,9223372036854775807,1"],
+            &[
+                "--engine",
+                "sparse",
+                "--random",
+                "0.5",
+                "--region",
+                "0,0,100000,100000",
+            ],
+            &["--cell-size", "0"],
+            &["--cell-size", "100000"],
+            &["--sheet-frames", "0"],
+            &["--sheet-columns", "0"],
+            &[
+                "--engine",
+                "sparse",
+                "--width",
+                "65535",
+                "--height",
+                "65535",
+                "--cell-size",
+                "16",
+                "--png",
+                "out.png",
+            ],
+            &[
+                "--engine", "hashlife", "--width", "10000", "--height", "10000", "--gif", "out.gif",
+            ],
+        ] {
+            assert_eq!(build(args).exit_code(), 3, "{args:?}");
+        }
+    }
+
+    #[test]
+    fn unreadable_patterns_have_exit_code_4() {
+        let error = build(&["--pattern", "/does/not/exist.rle"]);
+        assert!(matches!(error, ConfigError::PatternError(_)));
+        assert_eq!(error.exit_code(), 4);
+    }
+
+    #[test]
+    fn parse_errors_give_the_argument() {
+        let error = build(&["10", "abc", "0"]);
+        assert!(matches!(
+            error,
+            ConfigError::ArgsParsingError { argument, .. }
+                if argument == Argument { index: 2, text: String::from("abc") }
+        ));
+
+        let error = build(&["10", "10", "0", "1,1", "5x5"]);
+        assert!(matches!(
+            error,
+            ConfigError::StartingPointsParsingError { argument, reason }
+                if argument.index == 5 && argument.text == "5x5" && reason == "expected x,y"
+        ));
+
+        let error = build(&["--height", "10", "--width=abc"]);
+        assert!(matches!(
+            error,
+            ConfigError::ArgsParsingError { argument, .. } if argument.index == 3
+        ));
+    }
+
+    #[test]
+    fn cells_outside_the_grid_give_the_argument() {
+        let error = build(&["--width", "10", "--height", "10", "--cell", "50,50"]);
+        assert!(matches!(
+            &error,
+            ConfigError::CellOutOfBounds { error, argument: Some(argument) }
+                if (error.x, error.y) == (50, 50) && argument.index == 6
+        ));
+        assert_eq!(
+            error.to_string(),
+            "Starting point \"50,50\" (argument 6) is outside the 10x10 grid."
+        );
+
+        let error = ConfigBuilder::new(10, 10)
+            .with_cells(vec![(-1, 0)])
+            .build()
+            .err()
+            .unwrap();
+        assert!(matches!(
+            error,
+            ConfigError::CellOutOfBounds { argument: None, .. }
+        ));
+    }
+
+    #[test]
+    fn unbounded_engines_accept_cells_outside_the_grid() {
+        let args = ["10", "10", "0", "50,50", "--engine", "sparse"];
+        assert!(Config::build(args.iter().map(|&arg| String::from(arg)).collect()).is_ok());
+    }
+
+    #[test]
+    fn regions_beyond_the_largest_coordinates_are_invalid() {
+        let args = ["--engine", "sparse", "--random", "0.5", "--region"];
+        let error = build(&[&args[..], &["9223372036854775807,0,10,10"]].concat());
+        assert!(matches!(error, ConfigError::InvalidRegion(_)));
+    }
+
+    #[test]
+    fn unbounded_engines_accept_signed_cells() {
+        let args = [
+            "--engine",
+            "hashlife",
+            "--cell",
+            "-5,-9000000000",
+            "--cell",
+            "4,-3",
+        ];
+        let config = Config::build(args.iter().map(|&arg| String::from(arg)).collect()).unwrap();
+        assert_eq!(
+            config.get_starting_cells(),
+            vec![(-5, -9000000000), (4, -3)]
+        );
+
+        let error = build(&["--cell", "-1,0"]);
+        assert!(
+            matches!(error, ConfigError::CellOutOfBounds { argument: Some(argument), .. } if argument.index == 2)
+        );
+    }
+
+    #[test]
+    fn zero_size_grids_are_rejected() {
+        assert!(matches!(
+            build(&["--width", "0"]),
+            ConfigError::ZeroSizeGrid {
+                width: 0,
+                height: DEFAULT_HEIGHT
+            }
+        ));
+    }
+}
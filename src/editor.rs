@@ -0,0 +1,468 @@
+use std::{collections::BTreeSet, path::PathBuf};
+
+use crossterm::event::{KeyCode, KeyEvent, KeyEventKind, MouseButton, MouseEvent, MouseEventKind};
+
+use crate::{Pattern, Region, Rule};
+
+const SELECTED: &str = "\x1b[36m";
+const CURSOR: &str = "\x1b[7m";
+const RESET: &str = "\x1b[0m";
+
+/// What an [Editor] is currently doing with the cursor.
+#[derive(Debug, Clone, PartialEq)]
+enum Mode {
+    /// Toggling single cells.
+    Normal,
+    /// Drawing a line from `start` to the cursor.
+    Line { start: (i64, i64) },
+    /// Drawing a rectangle with opposite corners at `start` and the cursor.
+    Rectangle { start: (i64, i64), filled: bool },
+    /// Selecting the rectangle with opposite corners at `start` and the cursor.
+    Select { start: (i64, i64) },
+    /// Moving cells, stored relative to the cursor, which are put back at `origin` if the move is cancelled.
+    Move {
+        cells: Vec<(i64, i64)>,
+        origin: Option<(i64, i64)>,
+    },
+    /// Typing the path of the file to save the pattern to.
+    Save { path: String },
+}
+
+/// What should happen after an [Editor] handles an event.
+#[derive(Debug, Clone, PartialEq)]
+pub enum EditorAction {
+    /// Keep editing.
+    Continue,
+    /// Start the simulation from the given cells.
+    Start(Vec<(i64, i64)>),
+    /// Quit without starting the simulation.
+    Quit,
+}
+
+/// Edits the cells of a region of the grid with the keyboard or a mouse.
+///
+/// * Arrow keys - Move the cursor
+/// * `space` or `enter` - Toggle the cell under the cursor, or finish the current line, rectangle or move
+/// * `l` - Draw a line, `b` a rectangle and `f` a filled rectangle, from the cursor to where it is finished
+/// * `v` - Select a region, then press `m` to move, `c` to copy or `d` to delete the selected cells
+/// * `x` - Clear every cell in the view
+/// * `s` - Save the pattern to a file
+/// * `g` - Start the simulation from the edited cells
+/// * `esc` - Cancel the current operation
+/// * `q` - Quit
+///
+/// Clicking toggles a cell, and dragging draws or erases cells.
+///
+/// # Example
+/// ```
+/// use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+/// use game_of_life::{Editor, EditorAction, Region, Rule};
+///
+/// let view = Region { x: 0, y: 0, width: 10, height: 10 };
+/// let mut editor = Editor::new(Vec::new(), view, Rule::CONWAY);
+/// let mut press = |code| editor.handle_key(KeyEvent::new(code, KeyModifiers::NONE));
+///
+/// // Draw a line from 0,0 to 2,2
+/// press(KeyCode::Char('l'));
+/// press(KeyCode::Up);
+/// press(KeyCode::Up);
+/// press(KeyCode::Right);
+/// press(KeyCode::Right);
+/// press(KeyCode::Enter);
+///
+/// assert_eq!(press(KeyCode::Char('g')), EditorAction::Start(vec![(0, 0), (1, 1), (2, 2)]));
+/// ```
+pub struct Editor {
+    cells: BTreeSet<(i64, i64)>,
+    /// The region of the grid which is drawn and can be edited.
+    view: Region,
+    rule: Rule,
+    cursor: (i64, i64),
+    mode: Mode,
+    /// The path last saved to, which is suggested when saving again.
+    save_path: Option<PathBuf>,
+    /// The state cells are set to while the mouse is dragged, and where the mouse last was.
+    drag: Option<(bool, (i64, i64))>,
+    /// A message about the last save, shown in the status line.
+    message: Option<String>,
+}
+
+impl Editor {
+    /// Creates an editor which draws and edits the cells within `view`, with the cursor in the bottom-left corner.
+    ///
+    /// Cells outside `view` are kept as they are, and are included when the simulation is started or the pattern saved.
+    pub fn new(cells: Vec<(i64, i64)>, view: Region, rule: Rule) -> Self {
+        Self {
+            cells: cells.into_iter().collect(),
+            view,
+            rule,
+            cursor: (view.x, view.y),
+            mode: Mode::Normal,
+            save_path: None,
+            drag: None,
+            message: None,
+        }
+    }
+
+    /// Sets the path suggested when saving.
+    pub fn with_save_path(mut self, path: Option<PathBuf>) -> Self {
+        self.save_path = path;
+        self
+    }
+
+    /// Returns the live cells, sorted by `x` then `y`.
+    pub fn cells(&self) -> Vec<(i64, i64)> {
+        self.cells.iter().copied().collect()
+    }
+
+    /// Responds to a key press.
+    pub fn handle_key(&mut self, key: KeyEvent) -> EditorAction {
+        if key.kind != KeyEventKind::Press {
+            return EditorAction::Continue;
+        }
+
+        if let Mode::Save { path } = &mut self.mode {
+            match key.code {
+                KeyCode::Char(character) => path.push(character),
+                KeyCode::Backspace => {
+                    path.pop();
+                }
+                KeyCode::Enter => {
+                    let path = PathBuf::from(std::mem::take(path));
+                    self.save(path);
+                    self.mode = Mode::Normal;
+                }
+                KeyCode::Esc => self.mode = Mode::Normal,
+                _ => {}
+            }
+            return EditorAction::Continue;
+        }
+
+        match key.code {
+            KeyCode::Left => self.move_cursor(-1, 0),
+            KeyCode::Right => self.move_cursor(1, 0),
+            KeyCode::Up => self.move_cursor(0, 1),
+            KeyCode::Down => self.move_cursor(0, -1),
+            KeyCode::Char(' ') | KeyCode::Enter => self.apply(),
+            KeyCode::Esc => self.cancel(),
+            KeyCode::Char('q') => return EditorAction::Quit,
+            KeyCode::Char(character) => return self.command(character),
+            _ => {}
+        }
+        EditorAction::Continue
+    }
+
+    /// Responds to a mouse event, where the grid is drawn from the top-left corner of the terminal.
+    pub fn handle_mouse(&mut self, event: MouseEvent) {
+        if let MouseEventKind::Up(_) = event.kind {
+            self.drag = None;
+            return;
+        }
+        let Some(cell) = self.cell_at(event.column, event.row) else {
+            return;
+        };
+        self.cursor = cell;
+
+        match (event.kind, &self.mode) {
+            (MouseEventKind::Down(MouseButton::Left), Mode::Normal) => {
+                let alive = !self.cells.contains(&cell);
+                self.set(cell, alive);
+                self.drag = Some((alive, cell));
+            }
+            (MouseEventKind::Drag(MouseButton::Left), Mode::Normal) => {
+                if let Some((alive, previous)) = self.drag {
+                    for cell in line(previous, cell) {
+                        self.set(cell, alive);
+                    }
+                    self.drag = Some((alive, cell));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Returns the rows of the view from top to bottom with the cursor highlighted, followed by a status line.
+    pub fn lines(&self) -> Vec<String> {
+        let preview = self.preview();
+        let selection = match self.mode {
+            Mode::Select { start } => Some(bounds(start, self.cursor)),
+            _ => None,
+        };
+
+        let mut lines: Vec<String> = (self.view.y..self.view.y + self.view.height)
+            .rev()
+            .map(|y| {
+                (self.view.x..self.view.x + self.view.width)
+                    .map(|x| {
+                        let alive = self.cells.contains(&(x, y)) || preview.contains(&(x, y));
+                        let symbol = if alive { "O" } else { "." };
+                        if (x, y) == self.cursor {
+                            format!("{CURSOR}{symbol}{RESET}")
+                        } else if preview.contains(&(x, y))
+                            || selection.is_some_and(|region| region.contains(x, y))
+                        {
+                            format!("{SELECTED}{symbol}{RESET}")
+                        } else {
+                            symbol.to_string()
+                        }
+                    })
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            })
+            .collect();
+
+        lines.push(format!(
+            "Editing: {} cells | Cursor: {},{} | {}",
+            self.cells.len(),
+            self.cursor.0,
+            self.cursor.1,
+            self.message.as_deref().unwrap_or_default()
+        ));
+        lines.push(self.help());
+        lines
+    }
+
+    /// Returns the key help for the current mode.
+    fn help(&self) -> String {
+        match &self.mode {
+            Mode::Normal => {
+                "arrows: move  space: toggle  l: line  b/f: rectangle  v: select  x: clear  s: save  g: go  q: quit"
+                    .to_string()
+            }
+            Mode::Line { .. } | Mode::Rectangle { .. } => {
+                "arrows: move  space: draw  esc: cancel".to_string()
+            }
+            Mode::Select { .. } => {
+                "arrows: resize  m: move  c: copy  d: delete  esc: cancel".to_string()
+            }
+            Mode::Move { .. } => "arrows: move  space: place  esc: cancel".to_string(),
+            Mode::Save { path } => format!("Save as: {path}_  (enter: save  esc: cancel)"),
+        }
+    }
+
+    fn command(&mut self, character: char) -> EditorAction {
+        let cursor = self.cursor;
+        match (&self.mode, character) {
+            (Mode::Normal, 'g') => return EditorAction::Start(self.cells()),
+            (Mode::Normal, 'l') => self.mode = Mode::Line { start: cursor },
+            (Mode::Normal, 'b') => {
+                self.mode = Mode::Rectangle {
+                    start: cursor,
+                    filled: false,
+                }
+            }
+            (Mode::Normal, 'f') => {
+                self.mode = Mode::Rectangle {
+                    start: cursor,
+                    filled: true,
+                }
+            }
+            (Mode::Normal, 'v') => self.mode = Mode::Select { start: cursor },
+            (Mode::Normal, 'x') => {
+                let view = self.view;
+                self.cells.retain(|&(x, y)| !view.contains(x, y));
+            }
+            (Mode::Normal, 's') => {
+                let path = self.save_path.as_ref().map_or_else(
+                    || "pattern.rle".to_string(),
+                    |path| path.display().to_string(),
+                );
+                self.mode = Mode::Save { path };
+            }
+            (&Mode::Select { start }, 'm' | 'c' | 'd') => {
+                let region = bounds(start, cursor);
+                let selected: Vec<_> = self
+                    .cells
+                    .iter()
+                    .copied()
+                    .filter(|&(x, y)| region.contains(x, y))
+                    .collect();
+                if character != 'c' {
+                    for cell in &selected {
+                        self.cells.remove(cell);
+                    }
+                }
+                self.mode = if character == 'd' {
+                    Mode::Normal
+                } else {
+                    Mode::Move {
+                        cells: selected
+                            .iter()
+                            .map(|&(x, y)| (x - cursor.0, y - cursor.1))
+                            .collect(),
+                        origin: (character == 'm').then_some(cursor),
+                    }
+                };
+            }
+            _ => {}
+        }
+        EditorAction::Continue
+    }
+
+    /// Toggles the cell under the cursor, or finishes the current operation.
+    fn apply(&mut self) {
+        match self.mode {
+            Mode::Normal => {
+                let alive = !self.cells.contains(&self.cursor);
+                self.set(self.cursor, alive);
+            }
+            Mode::Select { .. } => {}
+            _ => {
+                for cell in self.preview() {
+                    self.set(cell, true);
+                }
+                self.mode = Mode::Normal;
+            }
+        }
+    }
+
+    /// Cancels the current operation, putting any cells being moved back where they were.
+    fn cancel(&mut self) {
+        if let Mode::Move {
+            cells,
+            origin: Some(origin),
+        } = &self.mode
+        {
+            let cells: Vec<_> = cells
+                .iter()
+                .map(|&(x, y)| (x + origin.0, y + origin.1))
+                .collect();
+            for cell in cells {
+                self.set(cell, true);
+            }
+        }
+        self.mode = Mode::Normal;
+    }
+
+    /// Returns the cells which would be drawn by finishing the current operation.
+    fn preview(&self) -> BTreeSet<(i64, i64)> {
+        let cursor = self.cursor;
+        let cells = match &self.mode {
+            &Mode::Line { start } => line(start, cursor),
+            &Mode::Rectangle { start, filled } => {
+                let region = bounds(start, cursor);
+                let (right, top) = (region.x + region.width - 1, region.y + region.height - 1);
+                (region.x..=right)
+                    .flat_map(|x| (region.y..=top).map(move |y| (x, y)))
+                    .filter(|&(x, y)| {
+                        filled || x == region.x || x == right || y == region.y || y == top
+                    })
+                    .collect()
+            }
+            Mode::Move { cells, .. } => cells
+                .iter()
+                .map(|&(x, y)| (x + cursor.0, y + cursor.1))
+                .collect(),
+            _ => Vec::new(),
+        };
+        cells
+            .into_iter()
+            .filter(|&(x, y)| self.view.contains(x, y))
+            .collect()
+    }
+
+    fn set(&mut self, cell: (i64, i64), alive: bool) {
+        if !self.view.contains(cell.0, cell.1) {
+            return;
+        }
+        if alive {
+            self.cells.insert(cell);
+        } else {
+            self.cells.remove(&cell);
+        }
+    }
+
+    fn move_cursor(&mut self, dx: i64, dy: i64) {
+        let (x, y) = self.cursor;
+        self.cursor = (
+            (x + dx).clamp(self.view.x, self.view.x + self.view.width - 1),
+            (y + dy).clamp(self.view.y, self.view.y + self.view.height - 1),
+        );
+    }
+
+    /// Returns the cell drawn at a terminal position, where cells are separated by spaces.
+    fn cell_at(&self, column: u16, row: u16) -> Option<(i64, i64)> {
+        let cell = (
+            self.view.x + column as i64 / 2,
+            self.view.y + self.view.height - 1 - row as i64,
+        );
+        self.view.contains(cell.0, cell.1).then_some(cell)
+    }
+
+    fn save(&mut self, path: PathBuf) {
+        let result = Pattern::from_cells(self.cells()).and_then(|mut pattern| {
+            pattern.set_rule(self.rule);
+            pattern.save(&path)
+        });
+        self.message = Some(match result {
+            Ok(()) => format!("Saved to {}", path.display()),
+            Err(err) => format!("Could not save: {err}"),
+        });
+        self.save_path = Some(path);
+    }
+}
+
+/// Returns the region with opposite corners at `a` and `b`.
+fn bounds(a: (i64, i64), b: (i64, i64)) -> Region {
+    Region {
+        x: a.0.min(b.0),
+        y: a.1.min(b.1),
+        width: (a.0 - b.0).abs() + 1,
+        height: (a.1 - b.1).abs() + 1,
+    }
+}
+
+/// Returns the cells on the line from `start` to `end`, using Bresenham's algorithm.
+fn line(start: (i64, i64), end: (i64, i64)) -> Vec<(i64, i64)> {
+    let (dx, dy) = ((end.0 - start.0).abs(), -(end.1 - start.1).abs());
+    let (step_x, step_y) = ((end.0 - start.0).signum(), (end.1 - start.1).signum());
+    let (mut x, mut y) = start;
+    let mut error = dx + dy;
+    let mut cells = vec![start];
+
+    while (x, y) != end {
+        let doubled = 2 * error;
+        if doubled >= dy {
+            error += dy;
+            x += step_x;
+        }
+        if doubled <= dx {
+            error += dx;
+            y += step_y;
+        }
+        cells.push((x, y));
+    }
+    cells
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::KeyModifiers;
+
+    fn press(editor: &mut Editor, code: KeyCode) -> EditorAction {
+        editor.handle_key(KeyEvent::new(code, KeyModifiers::NONE))
+    }
+
+    #[test]
+    fn keeps_cells_outside_the_view() {
+        let view = Region {
+            x: 0,
+            y: 0,
+            width: 5,
+            height: 5,
+        };
+        let mut editor = Editor::new(vec![(1, 1), (20, 20), (-3, 2)], view, Rule::CONWAY);
+
+        // Toggle 0,0 on and clear the view, leaving only the cells outside it
+        press(&mut editor, KeyCode::Enter);
+        assert_eq!(editor.cells(), vec![(-3, 2), (0, 0), (1, 1), (20, 20)]);
+        press(&mut editor, KeyCode::Char('x'));
+        press(&mut editor, KeyCode::Enter);
+
+        assert_eq!(
+            press(&mut editor, KeyCode::Char('g')),
+            EditorAction::Start(vec![(-3, 2), (0, 0), (20, 20)])
+        );
+    }
+}
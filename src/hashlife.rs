@@ -0,0 +1,498 @@
+use std::collections::HashMap;
+
+use crate::{CoordinateError, JumpTooLarge, Region, Rule, Universe};
+
+/// An index into [HashLife::nodes].
+type NodeId = u32;
+
+const DEAD: NodeId = 0;
+const ALIVE: NodeId = 1;
+
+/// The level of the largest root node, whose coordinates still fit in an `i64`.
+const MAX_LEVEL: u8 = 62;
+
+/// A square quadtree node of side `2^level`. Level `0` nodes are single cells.
+///
+/// `n` children are above `s` children, since `y` increases upwards.
+#[derive(Clone, Copy)]
+struct Node {
+    nw: NodeId,
+    ne: NodeId,
+    sw: NodeId,
+    se: NodeId,
+    level: u8,
+    population: u64,
+}
+
+/// An unbounded universe evolved with Gosper's HashLife algorithm.
+///
+/// Identical quadtree nodes are shared and the evolution of every node is memoized, so patterns with repetitive
+/// structure can be advanced by huge numbers of generations at once. Nodes and results are never evicted, so memory
+/// grows with the number of distinct nodes seen during a run.
+pub struct HashLife {
+    rule: Rule,
+    nodes: Vec<Node>,
+    /// Maps the children of a node to its id, so each distinct node is only stored once.
+    node_ids: HashMap<[NodeId; 4], NodeId>,
+    /// Maps a node and `j` to the centre of the node advanced by `2^j` generations.
+    results: HashMap<(NodeId, u8), NodeId>,
+    /// The empty node of each level.
+    empty: Vec<NodeId>,
+    root: NodeId,
+    /// The coordinates of the bottom-left cell of the root node.
+    origin: (i64, i64),
+}
+
+impl HashLife {
+    /// The most generations a pattern starting within 65536 cells of the origin can always be advanced by.
+    ///
+    /// # Example
+    /// A larger jump is refused, leaving the universe unchanged:
+    /// ```
+    /// use game_of_life::{HashLife, Rule, Universe};
+    ///
+    /// let glider = vec![(1, 2), (2, 1), (0, 0), (1, 0), (2, 0)];
+    /// let mut hashlife = HashLife::new(glider.clone(), Rule::CONWAY);
+    /// assert!(hashlife.advance(u64::MAX).is_err());
+    ///
+    /// let mut cells = hashlife.live_cells();
+    /// cells.sort();
+    /// assert_eq!(cells, vec![(0, 0), (1, 0), (1, 2), (2, 0), (2, 1)]);
+    ///
+    /// assert!(hashlife.advance(HashLife::MAX_GENERATIONS).is_ok());
+    /// assert_eq!(hashlife.population(), 5);
+    /// ```
+    pub const MAX_GENERATIONS: u64 = 1 << 56;
+
+    /// Creates a HashLife universe with the given cells alive.
+    ///
+    /// The rule must not cause cells with no live neighbours to be born, since that would fill the infinite plane.
+    /// Cells more than `2^61` cells from the origin cannot be represented, and are ignored.
+    ///
+    /// # Example
+    /// HashLife agrees with the naive [SparseGrid](crate::SparseGrid) and can jump far into the future:
+    /// ```
+    /// use game_of_life::{HashLife, Rule, SparseGrid, Universe};
+    ///
+    /// let r_pentomino = vec![(1, 0), (0, 1), (1, 1), (1, 2), (2, 2)];
+    /// let mut naive = SparseGrid::new(r_pentomino.clone(), Rule::CONWAY);
+    /// let mut hashlife = HashLife::new(r_pentomino, Rule::CONWAY);
+    /// for _ in 0..100 {
+    ///     naive.step_forward();
+    /// }
+    /// hashlife.advance(100).unwrap();
+    /// let (mut expected, mut cells) = (naive.live_cells(), hashlife.live_cells());
+    /// expected.sort();
+    /// cells.sort();
+    /// assert_eq!(cells, expected);
+    ///
+    /// let glider = vec![(1, 2), (2, 1), (0, 0), (1, 0), (2, 0)];
+    /// let mut hashlife = HashLife::new(glider.clone(), Rule::CONWAY);
+    /// hashlife.advance(1 << 40).unwrap();
+    /// let mut cells = hashlife.live_cells();
+    /// cells.sort();
+    /// let mut expected: Vec<(i64, i64)> = glider.iter().map(|&(x, y)| (x + (1 << 38), y - (1 << 38))).collect();
+    /// expected.sort();
+    /// assert_eq!(cells, expected);
+    /// ```
+    pub fn new(starting_cells: Vec<(i64, i64)>, rule: Rule) -> Self {
+        let leaf = |population| Node {
+            nw: DEAD,
+            ne: DEAD,
+            sw: DEAD,
+            se: DEAD,
+            level: 0,
+            population,
+        };
+
+        let mut hashlife = Self {
+            rule,
+            nodes: vec![leaf(0), leaf(1)],
+            node_ids: HashMap::new(),
+            results: HashMap::new(),
+            empty: vec![DEAD],
+            root: DEAD,
+            origin: (0, 0),
+        };
+
+        hashlife.root = hashlife.empty_node(3);
+        hashlife.origin = (-4, -4);
+        for (x, y) in starting_cells {
+            let _ = hashlife.set_cell(x, y, true);
+        }
+
+        hashlife
+    }
+
+    fn node(&self, id: NodeId) -> Node {
+        self.nodes[id as usize]
+    }
+
+    /// Returns the node with the given children, creating it if it does not exist yet.
+    fn join(&mut self, nw: NodeId, ne: NodeId, sw: NodeId, se: NodeId) -> NodeId {
+        if let Some(&id) = self.node_ids.get(&[nw, ne, sw, se]) {
+            return id;
+        }
+
+        let population = [nw, ne, sw, se]
+            .iter()
+            .map(|&child| self.node(child).population)
+            .sum();
+        let node = Node {
+            nw,
+            ne,
+            sw,
+            se,
+            level: self.node(nw).level + 1,
+            population,
+        };
+
+        let id = self.nodes.len() as NodeId;
+        self.nodes.push(node);
+        self.node_ids.insert([nw, ne, sw, se], id);
+        id
+    }
+
+    fn empty_node(&mut self, level: u8) -> NodeId {
+        while self.empty.len() <= level as usize {
+            let child = *self.empty.last().unwrap();
+            let id = self.join(child, child, child, child);
+            self.empty.push(id);
+        }
+        self.empty[level as usize]
+    }
+
+    /// Returns the side length of the root node.
+    fn size(&self) -> i64 {
+        1 << self.node(self.root).level
+    }
+
+    /// Doubles the size of the root node, keeping the existing contents in the centre, or returns `false` if it is
+    /// already the largest.
+    fn expand(&mut self) -> bool {
+        let root = self.node(self.root);
+        if root.level >= MAX_LEVEL {
+            return false;
+        }
+        let empty = self.empty_node(root.level - 1);
+
+        let nw = self.join(empty, empty, empty, root.nw);
+        let ne = self.join(empty, empty, root.ne, empty);
+        let sw = self.join(empty, root.sw, empty, empty);
+        let se = self.join(root.se, empty, empty, empty);
+
+        let half = self.size() / 2;
+        self.root = self.join(nw, ne, sw, se);
+        self.origin = (self.origin.0 - half, self.origin.1 - half);
+        true
+    }
+
+    /// Returns the region covered by the root node.
+    fn region(&self) -> Region {
+        Region {
+            x: self.origin.0,
+            y: self.origin.1,
+            width: self.size(),
+            height: self.size(),
+        }
+    }
+
+    fn set_cell(&mut self, x: i64, y: i64, alive: bool) -> Result<(), CoordinateError> {
+        if !alive && !self.region().contains(x, y) {
+            return Ok(());
+        }
+        while !self.region().contains(x, y) {
+            if !self.expand() {
+                return Err(CoordinateError {
+                    x,
+                    y,
+                    bounds: self.region(),
+                });
+            }
+        }
+
+        self.root = self.set_in_node(self.root, x - self.origin.0, y - self.origin.1, alive);
+        Ok(())
+    }
+
+    /// Returns a copy of `id` with the cell at the given offset from its bottom-left corner alive or dead.
+    fn set_in_node(&mut self, id: NodeId, x: i64, y: i64, alive: bool) -> NodeId {
+        let node = self.node(id);
+        if node.level == 0 {
+            return if alive { ALIVE } else { DEAD };
+        }
+
+        let half = 1 << (node.level - 1);
+        let (mut nw, mut ne, mut sw, mut se) = (node.nw, node.ne, node.sw, node.se);
+        match (x >= half, y >= half) {
+            (false, true) => nw = self.set_in_node(nw, x, y - half, alive),
+            (true, true) => ne = self.set_in_node(ne, x - half, y - half, alive),
+            (false, false) => sw = self.set_in_node(sw, x, y, alive),
+            (true, false) => se = self.set_in_node(se, x - half, y, alive),
+        }
+        self.join(nw, ne, sw, se)
+    }
+
+    /// Returns the level `n - 1` node at the centre of a level `n` node.
+    fn centre(&mut self, id: NodeId) -> NodeId {
+        let node = self.node(id);
+        let (nw, ne, sw, se) = (
+            self.node(node.nw),
+            self.node(node.ne),
+            self.node(node.sw),
+            self.node(node.se),
+        );
+        self.join(nw.se, ne.sw, sw.ne, se.nw)
+    }
+
+    /// Returns the node centred on the shared edge of two horizontally adjacent nodes.
+    fn centre_horizontal(&mut self, w: NodeId, e: NodeId) -> NodeId {
+        let (w, e) = (self.node(w), self.node(e));
+        self.join(w.ne, e.nw, w.se, e.sw)
+    }
+
+    /// Returns the node centred on the shared edge of two vertically adjacent nodes.
+    fn centre_vertical(&mut self, n: NodeId, s: NodeId) -> NodeId {
+        let (n, s) = (self.node(n), self.node(s));
+        self.join(n.sw, n.se, s.nw, s.ne)
+    }
+
+    /// Returns the centre of a level `n` node advanced by `2^j` generations, where `j <= n - 2`.
+    fn step(&mut self, id: NodeId, j: u8) -> NodeId {
+        let node = self.node(id);
+        if node.population == 0 {
+            return self.empty_node(node.level - 1);
+        }
+        if let Some(&result) = self.results.get(&(id, j)) {
+            return result;
+        }
+
+        let result = if node.level == 2 {
+            self.step_base(node)
+        } else {
+            let n00 = node.nw;
+            let n01 = self.centre_horizontal(node.nw, node.ne);
+            let n02 = node.ne;
+            let n10 = self.centre_vertical(node.nw, node.sw);
+            let n11 = self.centre(id);
+            let n12 = self.centre_vertical(node.ne, node.se);
+            let n20 = node.sw;
+            let n21 = self.centre_horizontal(node.sw, node.se);
+            let n22 = node.se;
+
+            let full_speed = j == node.level - 2;
+            let reduce = |hashlife: &mut Self, id| {
+                if full_speed {
+                    hashlife.step(id, j - 1)
+                } else {
+                    hashlife.centre(id)
+                }
+            };
+            let r = [n00, n01, n02, n10, n11, n12, n20, n21, n22].map(|id| reduce(self, id));
+
+            let nw = self.join(r[0], r[1], r[3], r[4]);
+            let ne = self.join(r[1], r[2], r[4], r[5]);
+            let sw = self.join(r[3], r[4], r[6], r[7]);
+            let se = self.join(r[4], r[5], r[7], r[8]);
+
+            let inner_j = if full_speed { j - 1 } else { j };
+            let nw = self.step(nw, inner_j);
+            let ne = self.step(ne, inner_j);
+            let sw = self.step(sw, inner_j);
+            let se = self.step(se, inner_j);
+            self.join(nw, ne, sw, se)
+        };
+
+        self.results.insert((id, j), result);
+        result
+    }
+
+    /// Advances the centre 2x2 cells of a 4x4 node by a single generation.
+    fn step_base(&mut self, node: Node) -> NodeId {
+        // cells[y][x], with `0,0` at the bottom-left
+        let mut cells = [[false; 4]; 4];
+        for (quadrant, (dx, dy)) in [
+            (node.nw, (0, 2)),
+            (node.ne, (2, 2)),
+            (node.sw, (0, 0)),
+            (node.se, (2, 0)),
+        ] {
+            let quadrant = self.node(quadrant);
+            cells[dy + 1][dx] = quadrant.nw == ALIVE;
+            cells[dy + 1][dx + 1] = quadrant.ne == ALIVE;
+            cells[dy][dx] = quadrant.sw == ALIVE;
+            cells[dy][dx + 1] = quadrant.se == ALIVE;
+        }
+
+        let next = |x: usize, y: usize| {
+            let live_neighbours = (y - 1..=y + 1)
+                .flat_map(|j| (x - 1..=x + 1).map(move |i| (i, j)))
+                .filter(|&(i, j)| (i, j) != (x, y) && cells[j][i])
+                .count() as u8;
+            let alive = if cells[y][x] {
+                self.rule.survives(live_neighbours)
+            } else {
+                self.rule.is_born(live_neighbours)
+            };
+            if alive {
+                ALIVE
+            } else {
+                DEAD
+            }
+        };
+
+        let (nw, ne, sw, se) = (next(1, 2), next(2, 2), next(1, 1), next(2, 1));
+        self.join(nw, ne, sw, se)
+    }
+
+    /// Advances the universe by `2^j` generations, or returns `false` if the root node would grow too large, in which
+    /// case it may have been expanded.
+    fn advance_power_of_two(&mut self, j: u8) -> bool {
+        loop {
+            let root = self.node(self.root);
+            let centre = self.centre(self.root);
+            if root.level >= j + 2 && self.node(centre).population == root.population {
+                break;
+            }
+            if !self.expand() {
+                return false;
+            }
+        }
+        if !self.expand() {
+            return false;
+        }
+
+        let quarter = self.size() / 4;
+        self.root = self.step(self.root, j);
+        self.origin = (self.origin.0 + quarter, self.origin.1 + quarter);
+        true
+    }
+
+    fn collect_cells(&self, id: NodeId, x: i64, y: i64, cells: &mut Vec<(i64, i64)>) {
+        let node = self.node(id);
+        if node.population == 0 {
+            return;
+        }
+        if node.level == 0 {
+            cells.push((x, y));
+            return;
+        }
+
+        let half = 1 << (node.level - 1);
+        self.collect_cells(node.nw, x, y + half, cells);
+        self.collect_cells(node.ne, x + half, y + half, cells);
+        self.collect_cells(node.sw, x, y, cells);
+        self.collect_cells(node.se, x + half, y, cells);
+    }
+}
+
+impl Universe for HashLife {
+    /// Advances the universe by one generation, or leaves it unchanged if cells could move beyond the coordinates it
+    /// can represent, as reported by [advance](Universe::advance).
+    fn step_forward(&mut self) {
+        let _ = self.advance(1);
+    }
+
+    /// Advances the universe by `generations` generations one power of two at a time, or returns a [JumpTooLarge] error
+    /// and leaves it unchanged if cells could move beyond the coordinates it can represent.
+    fn advance(&mut self, generations: u64) -> Result<(), JumpTooLarge> {
+        // Nodes are never changed once created, so the universe is restored by restoring its root
+        let (root, origin) = (self.root, self.origin);
+        for j in 0..u64::BITS as u8 {
+            if generations & (1 << j) != 0 && !self.advance_power_of_two(j) {
+                self.root = root;
+                self.origin = origin;
+                return Err(JumpTooLarge { generations });
+            }
+        }
+        Ok(())
+    }
+
+    fn is_alive(&self, x: i64, y: i64) -> bool {
+        if !self.region().contains(x, y) {
+            return false;
+        }
+
+        let (mut x, mut y) = (x - self.origin.0, y - self.origin.1);
+
+        let mut node = self.node(self.root);
+        while node.level > 0 {
+            if node.population == 0 {
+                return false;
+            }
+            let half = 1 << (node.level - 1);
+            let child = match (x >= half, y >= half) {
+                (false, true) => node.nw,
+                (true, true) => node.ne,
+                (false, false) => node.sw,
+                (true, false) => node.se,
+            };
+            x %= half;
+            y %= half;
+            node = self.node(child);
+        }
+        node.population == 1
+    }
+
+    fn set_alive(&mut self, x: i64, y: i64, alive: bool) -> Result<(), CoordinateError> {
+        self.set_cell(x, y, alive)
+    }
+
+    fn population(&self) -> u64 {
+        self.node(self.root).population
+    }
+
+    fn live_cells(&self) -> Vec<(i64, i64)> {
+        let mut cells = Vec::new();
+        self.collect_cells(self.root, self.origin.0, self.origin.1, &mut cells);
+        cells
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Grid, Topology};
+
+    #[test]
+    fn extreme_coordinates_are_outside_the_root() {
+        let mut hashlife = HashLife::new(vec![(0, 0)], Rule::CONWAY);
+        for (x, y) in [(i64::MAX, 0), (0, i64::MIN), (i64::MIN, i64::MAX)] {
+            assert!(!hashlife.is_alive(x, y));
+            assert_eq!(hashlife.set_alive(x, y, false), Ok(()));
+            assert!(hashlife.set_alive(x, y, true).is_err());
+        }
+        assert_eq!(hashlife.live_cells(), vec![(0, 0)]);
+    }
+
+    #[test]
+    fn hashlife_matches_grid_away_from_the_edges() {
+        let glider = [(1, 2), (2, 1), (0, 0), (1, 0), (2, 0)];
+        let r_pentomino = [(1, 2), (2, 2), (0, 1), (1, 1), (1, 0)];
+
+        for pattern in [glider, r_pentomino] {
+            let cells: Vec<(u8, u8)> = pattern.iter().map(|&(x, y)| (x + 78, y + 78)).collect();
+            let mut grid = Grid::new(160, 160, cells.clone(), Rule::CONWAY, Topology::Bounded);
+            let cells = cells.iter().map(|&(x, y)| (x as i64, y as i64)).collect();
+            let mut hashlife = HashLife::new(cells, Rule::CONWAY);
+
+            // Jumps of uneven lengths exercise both single steps and larger leaps
+            for jump in [1, 2, 3, 5, 8, 13, 21, 34, 55, 89] {
+                for _ in 0..jump {
+                    Universe::step_forward(&mut grid);
+                }
+                hashlife.advance(jump).unwrap();
+
+                let mut expected = grid.live_cells();
+                // The grid's edges would stop the pattern, so it must not have reached them
+                assert!(expected
+                    .iter()
+                    .all(|&(x, y)| (2..158).contains(&x) && (2..158).contains(&y)));
+                let mut actual = hashlife.live_cells();
+                expected.sort();
+                actual.sort();
+                assert_eq!(actual, expected, "generation {}", grid.generation());
+            }
+        }
+    }
+}
@@ -0,0 +1,467 @@
+use std::{
+    borrow::Cow,
+    fmt::{self, Display},
+    fs::File,
+    io::{self, BufWriter, Write},
+    path::Path,
+    str::FromStr,
+};
+
+use crate::{Game, Region, Universe};
+
+/// Digits from `0` to `9` in a 3x5 pixel font, one row per entry from the top, with the leftmost pixel in bit 2.
+const DIGITS: [[u8; 5]; 10] = [
+    [0b111, 0b101, 0b101, 0b101, 0b111],
+    [0b010, 0b110, 0b010, 0b010, 0b111],
+    [0b111, 0b001, 0b111, 0b100, 0b111],
+    [0b111, 0b001, 0b111, 0b001, 0b111],
+    [0b101, 0b101, 0b111, 0b001, 0b001],
+    [0b111, 0b100, 0b111, 0b001, 0b111],
+    [0b111, 0b100, 0b111, 0b101, 0b111],
+    [0b111, 0b001, 0b010, 0b010, 0b010],
+    [0b111, 0b101, 0b111, 0b101, 0b111],
+    [0b111, 0b101, 0b111, 0b001, 0b111],
+];
+
+/// An RGB colour, written as `#rrggbb`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Color {
+    pub red: u8,
+    pub green: u8,
+    pub blue: u8,
+}
+
+impl Color {
+    pub const BLACK: Color = Color::new(0, 0, 0);
+    pub const WHITE: Color = Color::new(255, 255, 255);
+    pub const RED: Color = Color::new(220, 40, 40);
+
+    pub const fn new(red: u8, green: u8, blue: u8) -> Self {
+        Self { red, green, blue }
+    }
+
+    fn to_bytes(self) -> [u8; 3] {
+        [self.red, self.green, self.blue]
+    }
+}
+
+impl FromStr for Color {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let hex = s.strip_prefix('#').unwrap_or(s);
+        let channel = |index: usize| {
+            hex.get(index..index + 2)
+                .and_then(|channel| u8::from_str_radix(channel, 16).ok())
+                .ok_or_else(|| s.to_string())
+        };
+        if hex.len() != 6 {
+            return Err(s.to_string());
+        }
+        Ok(Color::new(channel(0)?, channel(2)?, channel(4)?))
+    }
+}
+
+impl Display for Color {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "#{:02x}{:02x}{:02x}", self.red, self.green, self.blue)
+    }
+}
+
+/// The size and colours of the cells in an exported image.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ImageStyle {
+    /// The width and height of each cell, in pixels.
+    cell_size: u32,
+    alive: Color,
+    dead: Color,
+    /// The colour of the generation number drawn over animation frames, if any.
+    overlay: Option<Color>,
+}
+
+impl Default for ImageStyle {
+    fn default() -> Self {
+        Self {
+            cell_size: 4,
+            alive: Color::BLACK,
+            dead: Color::WHITE,
+            overlay: None,
+        }
+    }
+}
+
+impl ImageStyle {
+    /// The largest cell size, which keeps a 255x255 grid within the 65535 pixel limit of a GIF.
+    pub const MAX_CELL_SIZE: u32 = 256;
+
+    /// The largest width or height of an image, which is the limit of a GIF.
+    pub const MAX_IMAGE_SIZE: u32 = u16::MAX as u32;
+
+    /// The most pixels in an image, which is held in memory while it is written.
+    pub const MAX_PIXELS: u64 = 1 << 26;
+
+    /// Returns `true` if an image `width` by `height` pixels is within [MAX_IMAGE_SIZE](Self::MAX_IMAGE_SIZE) and
+    /// [MAX_PIXELS](Self::MAX_PIXELS).
+    pub fn fits(width: u64, height: u64) -> bool {
+        width <= Self::MAX_IMAGE_SIZE as u64
+            && height <= Self::MAX_IMAGE_SIZE as u64
+            && width * height <= Self::MAX_PIXELS
+    }
+
+    /// Sets the width and height of each cell in pixels, clamped between 1 and [MAX_CELL_SIZE](Self::MAX_CELL_SIZE).
+    pub fn with_cell_size(mut self, cell_size: u32) -> Self {
+        self.cell_size = cell_size.clamp(1, Self::MAX_CELL_SIZE);
+        self
+    }
+
+    pub fn with_colors(mut self, alive: Color, dead: Color) -> Self {
+        self.alive = alive;
+        self.dead = dead;
+        self
+    }
+
+    /// Draws the generation number in the top-left corner of each animation frame in the given colour.
+    pub fn with_overlay(mut self, overlay: Option<Color>) -> Self {
+        self.overlay = overlay;
+        self
+    }
+
+    pub fn cell_size(&self) -> u32 {
+        self.cell_size
+    }
+
+    /// Returns the size in pixels of an image of `region`, or an error if it does not [fit](Self::fits).
+    fn image_size(&self, region: Region) -> io::Result<(u32, u32)> {
+        let pixels = |cells: i64| (cells.max(1) as u64).checked_mul(self.cell_size as u64);
+        match (pixels(region.width), pixels(region.height)) {
+            (Some(width), Some(height)) if Self::fits(width, height) => {
+                Ok((width as u32, height as u32))
+            }
+            _ => Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "an image of {}x{} cells of {} pixels is too large",
+                    region.width, region.height, self.cell_size
+                ),
+            )),
+        }
+    }
+
+    /// Returns one palette index per pixel of an image of `region`, from the top row down, where `0` is dead and `1`
+    /// is alive.
+    fn indexed_pixels<U: Universe + ?Sized>(
+        &self,
+        universe: &U,
+        region: Region,
+    ) -> io::Result<Vec<u8>> {
+        let (width, height) = self.image_size(region)?;
+        let mut pixels = vec![0; width as usize * height as usize];
+        let cell_size = self.cell_size as usize;
+
+        for (x, y) in universe.live_cells() {
+            if !region.contains(x, y) {
+                continue;
+            }
+            let left = (x - region.x) as usize * cell_size;
+            let top = (region.y + region.height - 1 - y) as usize * cell_size;
+            for row in top..top + cell_size {
+                let start = row * width as usize + left;
+                pixels[start..start + cell_size].fill(1);
+            }
+        }
+        Ok(pixels)
+    }
+
+    /// Renders `region` of a universe as an image, with `y` increasing upwards, or returns an error if the image would
+    /// be larger than [MAX_IMAGE_SIZE](Self::MAX_IMAGE_SIZE) or [MAX_PIXELS](Self::MAX_PIXELS).
+    ///
+    /// # Example
+    /// ```
+    /// use game_of_life::{Color, ImageStyle, Region, Rule, SparseGrid};
+    ///
+    /// let grid = SparseGrid::new(vec![(0, 0)], Rule::CONWAY);
+    /// let style = ImageStyle::default().with_cell_size(2).with_colors(Color::BLACK, Color::WHITE);
+    /// let image = style.render(&grid, Region { x: 0, y: 0, width: 2, height: 1 }).unwrap();
+    ///
+    /// assert_eq!((image.width(), image.height()), (4, 2));
+    /// assert_eq!(image.pixel(0, 1), Color::BLACK);
+    /// assert_eq!(image.pixel(2, 1), Color::WHITE);
+    ///
+    /// let huge = Region { x: 0, y: 0, width: i64::MAX, height: 1 };
+    /// assert!(style.render(&grid, huge).is_err());
+    /// ```
+    pub fn render<U: Universe + ?Sized>(&self, universe: &U, region: Region) -> io::Result<Image> {
+        let (width, height) = self.image_size(region)?;
+        let palette = [self.dead.to_bytes(), self.alive.to_bytes()];
+        let pixels = self
+            .indexed_pixels(universe, region)?
+            .into_iter()
+            .flat_map(|index| palette[index as usize])
+            .collect();
+        Ok(Image {
+            width,
+            height,
+            pixels,
+        })
+    }
+}
+
+/// An RGB image of a universe.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Image {
+    width: u32,
+    height: u32,
+    /// Three bytes per pixel, from the top row down.
+    pixels: Vec<u8>,
+}
+
+impl Image {
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// Returns the colour of the pixel at the given coordinates, where `0,0` is the top-left pixel.
+    pub fn pixel(&self, x: u32, y: u32) -> Color {
+        let index = (y as usize * self.width as usize + x as usize) * 3;
+        Color::new(
+            self.pixels[index],
+            self.pixels[index + 1],
+            self.pixels[index + 2],
+        )
+    }
+
+    /// Writes the image in PNG format.
+    pub fn write_png<W: Write>(&self, output: W) -> io::Result<()> {
+        let mut encoder = png::Encoder::new(output, self.width, self.height);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder.write_header().map_err(io::Error::other)?;
+        writer
+            .write_image_data(&self.pixels)
+            .map_err(io::Error::other)?;
+        writer.finish().map_err(io::Error::other)
+    }
+
+    /// Saves the image to a PNG file.
+    pub fn save_png<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        let mut output = BufWriter::new(File::create(path)?);
+        self.write_png(&mut output)?;
+        output.flush()
+    }
+}
+
+/// Records each generation of a [Game](crate::Game) into an animation or image.
+pub trait Recorder {
+    /// Adds a frame showing the current state of a universe.
+    fn add_frame(&mut self, universe: &dyn Universe, generation: u64) -> io::Result<()>;
+
+    /// Writes any remaining output once every frame has been added.
+    fn finish(self: Box<Self>) -> io::Result<()>;
+}
+
+/// Drives a set of [Recorders](Recorder), which are given a frame whenever the caller adds one after advancing a
+/// [Game], so that advancing the game itself does no I/O.
+///
+/// The first error which occurs while recording stops every recorder, and is returned by
+/// [finish](Recording::finish).
+///
+/// # Example
+/// ```
+/// use game_of_life::{Config, Game, Recording, StatsFormat, StatsRecorder};
+///
+/// let args = vec!["10", "10", "0", "4,4", "5,4", "6,4"];
+/// let mut game = Game::new(Config::build(args.into_iter().map(String::from).collect()).unwrap());
+/// let mut recording = Recording::new();
+/// recording.add(Box::new(StatsRecorder::new(Vec::new(), StatsFormat::Csv)), &game);
+/// for _ in 0..3 {
+///     game.tick().unwrap();
+///     recording.add_frame(&game);
+/// }
+/// recording.finish().unwrap();
+/// ```
+#[derive(Default)]
+pub struct Recording {
+    recorders: Vec<Box<dyn Recorder>>,
+    /// The first error which occurred while recording, which stops every recorder.
+    error: Option<io::Error>,
+}
+
+impl Recording {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Starts recording into `recorder`, beginning with the current generation of `game`.
+    pub fn add(&mut self, mut recorder: Box<dyn Recorder>, game: &Game) {
+        if self.error.is_some() {
+            return;
+        }
+        match recorder.add_frame(game.universe(), game.generation()) {
+            Ok(()) => self.recorders.push(recorder),
+            Err(error) => self.stop(error),
+        }
+    }
+
+    /// Returns `true` if nothing is being recorded.
+    pub fn is_empty(&self) -> bool {
+        self.recorders.is_empty()
+    }
+
+    /// Adds the current generation of `game` to every recorder.
+    pub fn add_frame(&mut self, game: &Game) {
+        let result = self
+            .recorders
+            .iter_mut()
+            .try_for_each(|recorder| recorder.add_frame(game.universe(), game.generation()));
+        if let Err(error) = result {
+            self.stop(error);
+        }
+    }
+
+    /// Finishes every recorder, returning the first error which occurred while recording, if any.
+    pub fn finish(self) -> io::Result<()> {
+        if let Some(error) = self.error {
+            return Err(error);
+        }
+        for recorder in self.recorders {
+            recorder.finish()?;
+        }
+        Ok(())
+    }
+
+    fn stop(&mut self, error: io::Error) {
+        self.recorders.clear();
+        self.error = Some(error);
+    }
+}
+
+/// Records frames of a universe into an animated GIF which loops forever.
+///
+/// # Example
+/// ```
+/// use game_of_life::{GifRecorder, ImageStyle, Region, Rule, SparseGrid, Universe};
+///
+/// let mut grid = SparseGrid::new(vec![(1, 0), (1, 1), (1, 2)], Rule::CONWAY);
+/// let region = Region { x: 0, y: 0, width: 3, height: 3 };
+/// let mut output = Vec::new();
+///
+/// let mut recorder = GifRecorder::new(&mut output, ImageStyle::default(), region, 100).unwrap();
+/// for generation in 0..4 {
+///     recorder.add_frame(&grid, generation).unwrap();
+///     grid.step_forward();
+/// }
+/// recorder.finish().unwrap();
+/// assert!(output.starts_with(b"GIF89a"));
+/// ```
+pub struct GifRecorder<W: Write> {
+    encoder: gif::Encoder<W>,
+    style: ImageStyle,
+    region: Region,
+    /// The time each frame is shown for, in hundredths of a second.
+    delay: u16,
+}
+
+impl GifRecorder<BufWriter<File>> {
+    /// Creates a GIF file which frames of `region` are recorded into, each shown for `delay` milliseconds.
+    pub fn create<P: AsRef<Path>>(
+        path: P,
+        style: ImageStyle,
+        region: Region,
+        delay: u64,
+    ) -> io::Result<Self> {
+        Self::new(BufWriter::new(File::create(path)?), style, region, delay)
+    }
+}
+
+impl<W: Write> GifRecorder<W> {
+    /// Creates a recorder which writes frames of `region` to `output`, each shown for `delay` milliseconds.
+    pub fn new(output: W, style: ImageStyle, region: Region, delay: u64) -> io::Result<Self> {
+        // Images which fit are never wider or taller than a GIF can be
+        let (width, height) = style.image_size(region)?;
+        let (width, height) = (width as u16, height as u16);
+
+        let overlay = style.overlay.unwrap_or(Color::RED);
+        let palette: Vec<u8> = [style.dead, style.alive, overlay]
+            .into_iter()
+            .flat_map(Color::to_bytes)
+            .collect();
+        let mut encoder =
+            gif::Encoder::new(output, width, height, &palette).map_err(io::Error::other)?;
+        encoder
+            .set_repeat(gif::Repeat::Infinite)
+            .map_err(io::Error::other)?;
+
+        Ok(Self {
+            encoder,
+            style,
+            region,
+            delay: (delay / 10).min(u16::MAX as u64) as u16,
+        })
+    }
+
+    /// Adds a frame showing the current state of a universe, labelled with `generation` if the style has an overlay.
+    pub fn add_frame<U: Universe + ?Sized>(
+        &mut self,
+        universe: &U,
+        generation: u64,
+    ) -> io::Result<()> {
+        let (width, height) = self.style.image_size(self.region)?;
+        let mut pixels = self.style.indexed_pixels(universe, self.region)?;
+        if self.style.overlay.is_some() {
+            draw_number(&mut pixels, width as usize, height as usize, generation);
+        }
+
+        let frame = gif::Frame {
+            width: width as u16,
+            height: height as u16,
+            delay: self.delay,
+            buffer: Cow::Owned(pixels),
+            ..gif::Frame::default()
+        };
+        self.encoder.write_frame(&frame).map_err(io::Error::other)
+    }
+
+    /// Writes the end of the GIF and flushes it.
+    pub fn finish(self) -> io::Result<()> {
+        self.encoder.into_inner()?.flush()
+    }
+}
+
+impl<W: Write> Recorder for GifRecorder<W> {
+    fn add_frame(&mut self, universe: &dyn Universe, generation: u64) -> io::Result<()> {
+        GifRecorder::add_frame(self, universe, generation)
+    }
+
+    fn finish(self: Box<Self>) -> io::Result<()> {
+        GifRecorder::finish(*self)
+    }
+}
+
+/// Draws a number in palette colour `2` in the top-left corner of indexed pixels, scaled to suit the image.
+fn draw_number(pixels: &mut [u8], width: usize, height: usize, number: u64) {
+    let scale = (width.min(height) / 64).max(1);
+    let margin = scale;
+
+    for (position, digit) in number.to_string().bytes().enumerate() {
+        let glyph = DIGITS[(digit - b'0') as usize];
+        let left = margin + position * 4 * scale;
+        for (row, bits) in glyph.iter().enumerate() {
+            for column in 0..3 {
+                if bits & (0b100 >> column) == 0 {
+                    continue;
+                }
+                for y in margin + row * scale..margin + (row + 1) * scale {
+                    for x in left + column * scale..left + (column + 1) * scale {
+                        if x < width && y < height {
+                            pixels[y * width + x] = 2;
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
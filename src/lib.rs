@@ -1,44 +1,68 @@
-use std::{
-    fmt::{self, Display},
-    num::ParseIntError,
-};
-
-pub type ConfigResult<T> = std::result::Result<T, ConfigError>;
-
-#[derive(Debug, Clone)]
-pub enum ConfigError {
-    ArgsParsingError(ParseIntError),
-    StartingSizeMissing,
-    CycleCountMissing,
-    TooFewStartingPoints,
-    StartingPointsParsingError,
-}
-
-impl std::error::Error for ConfigError {}
-
-impl From<ParseIntError> for ConfigError {
-    fn from(err: ParseIntError) -> Self {
-        ConfigError::ArgsParsingError(err)
-    }
-}
-
-impl Display for ConfigError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            ConfigError::ArgsParsingError(parse_int_error) => {
-                write!(f, "Could not parse arguments: {parse_int_error}")
-            }
-            ConfigError::StartingSizeMissing => write!(f, "No starting size provided."),
-            ConfigError::CycleCountMissing => write!(f, "No cycle count provided."),
-            ConfigError::TooFewStartingPoints => write!(f, "Not enough starting points provided."),
-            ConfigError::StartingPointsParsingError => write!(f, "Cannot parse starting points"),
-        }
-    }
-}
+use std::{fmt, io, path::PathBuf, thread};
+
+mod cli;
+mod color;
+mod config;
+mod editor;
+mod hashlife;
+mod image;
+mod packed;
+mod pattern;
+mod period;
+mod random;
+mod render;
+mod rule;
+mod search;
+mod sparse;
+mod stats;
+mod svg;
+#[cfg(test)]
+mod testing;
+mod topology;
+mod tui;
+mod universe;
+mod viewport;
+
+pub use cli::{completions, help, version, Command, Shell};
+pub use color::{age_color, colored_frame_lines, ColorMode};
+pub use config::{Argument, Config, ConfigBuilder, ConfigError, ConfigResult, PatternSource};
+pub use editor::{Editor, EditorAction};
+pub use hashlife::HashLife;
+pub use image::{Color, GifRecorder, Image, ImageStyle, Recorder, Recording};
+pub use packed::PackedGrid;
+pub use pattern::{Pattern, PatternError, PatternFormat};
+pub use period::{PeriodDetector, Stability};
+pub use random::{random_soup, Random};
+pub use render::{frame_lines, AnsiRenderer, Renderer};
+pub use rule::{Rule, RuleError};
+pub use search::{Search, SearchTarget, SoupResult};
+pub use sparse::SparseGrid;
+pub use stats::{GenerationStats, StatsFormat, StatsRecorder};
+pub use svg::{SvgSheet, SvgStyle};
+pub use topology::Topology;
+pub use tui::Tui;
+pub use universe::{CellAge, CoordinateError, Engine, JumpTooLarge, Region, Universe};
+pub use viewport::{Glyphs, Viewport};
+
+use universe::{resolve_thread_count, rows_per_band};
 
 /// A struct representing the Game of Life game state.
 pub struct Game {
-    grid: Grid,
+    grid: Box<dyn Universe>,
+    rule: Rule,
+    /// The part of the grid which is drawn.
+    viewport: Viewport,
+    /// The seed of the random soup, which is shown beneath the grid.
+    seed: Option<u64>,
+    generation: u64,
+    /// The front end frames are drawn to, which is the terminal unless [replaced](Game::with_renderer).
+    renderer: Box<dyn Renderer>,
+    /// The config the game was created from, used to [reset](Game::reset) it.
+    config: Config,
+    /// Detects when the pattern settles, if enabled with [detect_periods](Game::detect_periods).
+    detector: Option<PeriodDetector>,
+    /// How the pattern behaves once it has settled.
+    stability: Option<Stability>,
 }
 
 impl Game {
@@ -47,239 +71,691 @@ impl Game {
     /// # Arguments
     /// * `config` - A [Config] object
     pub fn new(config: Config) -> Self {
-        let x = config.get_x();
-        let y = config.get_y();
-        let starting_cells = config.get_starting_cells();
-
         Self {
-            grid: Grid::new(x, y, starting_cells),
+            grid: build_universe(&config),
+            rule: config.get_rule(),
+            viewport: build_viewport(&config),
+            seed: config.get_seed(),
+            generation: 0,
+            renderer: Box::new(AnsiRenderer::stdout()),
+            config,
+            detector: None,
+            stability: None,
         }
     }
 
-    /// Runs one game cycle.
-    fn step_forward(&mut self) {
-        self.grid.step_forward();
+    /// Draws frames to `renderer` instead of the terminal.
+    pub fn with_renderer<R: Renderer + 'static>(mut self, renderer: R) -> Self {
+        self.renderer = Box::new(renderer);
+        self
     }
 
-    /// Prints the current game state to the console.
-    pub fn print_game_state(&self) {
-        self.grid.print_grid();
+    /// Runs one game cycle without drawing or recording anything, or returns a [JumpTooLarge] error and leaves the game
+    /// unchanged if cells would move beyond the largest coordinates.
+    pub fn tick(&mut self) -> Result<(), JumpTooLarge> {
+        self.grid.advance(1)?;
+        self.generation += 1;
+        self.observe();
+        Ok(())
     }
 
-    /// Runs one cycle and prints the new game state to the console.
-    pub fn step(&mut self) {
-        self.step_forward();
-        self.print_game_state();
+    /// Starts detecting when the pattern settles into a still life, oscillator or spaceship, which is then returned by
+    /// [stability](Game::stability).
+    ///
+    /// # Example
+    /// ```
+    /// use game_of_life::{Config, Game, Stability};
+    ///
+    /// let args = vec!["10", "10", "0", "4,4", "5,4", "6,4"];
+    /// let mut game = Game::new(Config::build(args.into_iter().map(String::from).collect()).unwrap());
+    /// game.detect_periods();
+    /// game.advance(2).unwrap();
+    /// assert_eq!(game.stability(), Some(Stability::Oscillator { period: 2 }));
+    /// ```
+    pub fn detect_periods(&mut self) {
+        if self.detector.is_none() {
+            self.detector = Some(PeriodDetector::new());
+            self.observe();
+        }
     }
-}
 
-pub struct Config {
-    grid_width: u8,
-    grid_height: u8,
-    /// The number of cycles to complete, or `0` to run indefinitely
-    cycle_count: usize,
-    /// A vector of coordinates of cells which should start in an alive state
-    starting_cells: Vec<(u8, u8)>,
-}
+    /// Returns `true` if the game has been asked to [detect periods](Game::detect_periods).
+    pub fn is_detecting_periods(&self) -> bool {
+        self.detector.is_some()
+    }
+
+    /// Returns how the pattern behaves once it has settled, or `None` if it has not settled or periods are not being
+    /// detected.
+    pub fn stability(&self) -> Option<Stability> {
+        self.stability
+    }
+
+    fn observe(&mut self) {
+        if self.stability.is_some() {
+            return;
+        }
+        if let Some(detector) = &mut self.detector {
+            self.stability = detector.observe(self.generation, &self.grid.live_cells());
+        }
+    }
+
+    /// Returns the region covered by the grid, which is also the region printed by unbounded engines.
+    pub fn grid_region(&self) -> Region {
+        grid_region(&self.config)
+    }
 
-impl Config {
-    /// Builds and returns the [Config] object, or a [ConfigError].
+    /// Renders the whole grid as an image, or returns an error if the image would be too large.
+    pub fn to_image(&self, style: &ImageStyle) -> io::Result<Image> {
+        style.render(&*self.grid, self.grid_region())
+    }
+
+    /// Renders the whole grid as an SVG image.
+    pub fn to_svg(&self, style: &SvgStyle) -> String {
+        style.render(&*self.grid, self.grid_region())
+    }
+
+    /// Replaces the starting cells, then resets the game to them.
+    pub fn set_starting_cells(&mut self, cells: Vec<(i64, i64)>) {
+        self.config.set_starting_cells(cells);
+        self.reset();
+    }
+
+    /// Restores the starting cells from the game's [Config] and resets the generation count.
+    pub fn reset(&mut self) {
+        self.grid = build_universe(&self.config);
+        self.generation = 0;
+        self.restart_detection();
+    }
+
+    /// Forgets any earlier generations seen while detecting periods, since the pattern has been changed.
+    fn restart_detection(&mut self) {
+        self.stability = None;
+        if let Some(detector) = &mut self.detector {
+            detector.clear();
+        }
+        self.observe();
+    }
+
+    /// Returns the number of generations since the game started or was last reset.
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    pub fn rule(&self) -> Rule {
+        self.rule
+    }
+
+    /// Returns the region of the grid which is drawn.
+    pub fn view(&self) -> Region {
+        self.viewport.region()
+    }
+
+    pub fn viewport(&self) -> &Viewport {
+        &self.viewport
+    }
+
+    pub fn viewport_mut(&mut self) -> &mut Viewport {
+        &mut self.viewport
+    }
+
+    /// Returns the universe storing the game state, as given to each [Recorder].
+    pub fn universe(&self) -> &dyn Universe {
+        &*self.grid
+    }
+
+    /// Returns the coordinates of every live cell.
+    pub fn live_cells(&self) -> impl Iterator<Item = (i64, i64)> {
+        self.grid.live_cells().into_iter()
+    }
+
+    /// Returns `true` if the cell at the given coordinates is alive, or a [CoordinateError] if they point outside a
+    /// fixed-size grid.
     ///
-    /// # Arguments
+    /// # Example
+    /// ```
+    /// use game_of_life::{Config, Game};
     ///
-    /// * `args` - A vector with a minimum length of five containing the config arguments:
-    ///     * `0` - Grid width, in cells
-    ///     * `1` - Grid height, in cells
-    ///     * `2` - Cycle count, or `0` to run indefinitely
-    ///     * `3+` - At least three starting coordinates, each in the format `x,y`, where `0,0` is the bottom-left cell
-    ///             (this is the minimum number of cells required to create a sustained game)
+    /// let args = vec!["10", "10", "0", "4,4", "5,4", "6,4"];
+    /// let mut game = Game::new(Config::build(args.into_iter().map(String::from).collect()).unwrap());
+    /// assert_eq!(game.is_alive(5, 4), Ok(true));
     ///
+    /// game.set(5, 4, false).unwrap();
+    /// game.set(9, 9, true).unwrap();
+    /// assert_eq!(game.population(), 3);
+    /// assert_eq!(game.live_cells().filter(|&(x, _)| x == 9).count(), 1);
+    ///
+    /// assert!(game.is_alive(10, 0).is_err());
+    /// assert!(game.set(-1, 0, true).is_err());
+    /// ```
+    pub fn is_alive(&self, x: i64, y: i64) -> Result<bool, CoordinateError> {
+        if let Some(bounds) = self.grid.bounds() {
+            CoordinateError::check(x, y, bounds)?;
+        }
+        Ok(self.grid.is_alive(x, y))
+    }
+
+    /// Makes the cell at the given coordinates alive or dead, or returns a [CoordinateError] if they point outside a
+    /// fixed-size grid.
+    ///
+    /// The starting cells are unchanged, so [reset](Game::reset) undoes any changes.
+    pub fn set(&mut self, x: i64, y: i64, alive: bool) -> Result<(), CoordinateError> {
+        self.grid.set_alive(x, y, alive)?;
+        self.restart_detection();
+        Ok(())
+    }
+
+    /// Returns the width of the grid, which is also the width printed by unbounded engines.
+    pub fn width(&self) -> u16 {
+        self.config.get_x()
+    }
+
+    /// Returns the height of the grid, which is also the height printed by unbounded engines.
+    pub fn height(&self) -> u16 {
+        self.config.get_y()
+    }
+
+    /// Returns the file the final game state is saved to, if any.
+    pub fn save_path(&self) -> Option<PathBuf> {
+        self.config.get_save_path()
+    }
+
+    /// Advances the game by `generations` generations without printing or recording anything.
+    ///
+    /// With the HashLife engine this takes time roughly logarithmic in `generations` for most patterns, unless periods
+    /// are being [detected](Game::detect_periods), in which case every generation is stepped through. To
+    /// [record](Recording) every generation, advance by one at a time.
+    ///
+    /// Returns a [JumpTooLarge] error if cells would move beyond the largest coordinates, in which case the game is
+    /// left at the last generation which could be reached, and its [generation](Game::generation) count matches it.
     ///
     /// # Example
     /// ```
-    /// use game_of_life::config::Config;
+    /// use game_of_life::{ConfigBuilder, Engine, Game};
+    ///
+    /// let glider = vec![(1, 2), (2, 1), (0, 0), (1, 0), (2, 0)];
+    /// let config = ConfigBuilder::new(10, 10).with_engine(Engine::HashLife).with_cells(glider);
+    /// let mut game = Game::new(config.build().unwrap());
+    /// assert!(game.advance(u64::MAX).is_err());
+    /// assert_eq!(game.generation(), 0);
     ///
-    /// let args = vec![String::from("10"), String::from("10"), String::from("2,4"), String::from("2,5"), String::from("3,5")];
-    /// let config = Config::build(args);
+    /// game.advance(1 << 40).unwrap();
+    /// assert_eq!(game.generation(), 1 << 40);
     /// ```
-    pub fn build(args: Vec<String>) -> ConfigResult<Self> {
-        if args.len() < 2 {
-            return Err(ConfigError::StartingSizeMissing);
-        } else if args.len() < 3 {
-            return Err(ConfigError::CycleCountMissing);
-        } else if args.len() < 6 {
-            return Err(ConfigError::TooFewStartingPoints);
+    pub fn advance(&mut self, generations: u64) -> Result<(), JumpTooLarge> {
+        let detecting = self.detector.is_some() && self.stability.is_none();
+        if detecting {
+            for _ in 0..generations {
+                self.tick()?;
+            }
+        } else {
+            self.grid.advance(generations)?;
+            self.generation += generations;
         }
+        Ok(())
+    }
 
-        let grid_width: u8 = args[0].parse()?;
-        let grid_height: u8 = args[1].parse()?;
-        let cycle_count: usize = args[2].parse()?;
-        let mut starting_cells: Vec<(u8, u8)> = Vec::new();
+    /// Returns the number of live cells.
+    pub fn population(&self) -> u64 {
+        self.grid.population()
+    }
 
-        let starting_cells_strings = &args[3..];
+    /// Returns the smallest region containing every live cell, or `None` if there are none.
+    pub fn bounding_box(&self) -> Option<Region> {
+        stats::bounding_box(&self.grid.live_cells())
+    }
 
-        for cell_string in starting_cells_strings {
-            let components: Vec<&str> = cell_string.split(',').collect();
+    /// Returns the live cells of the current game state as a [Pattern], or [PatternError::TooLarge] if they are too far
+    /// apart to represent.
+    pub fn to_pattern(&self) -> Result<Pattern, PatternError> {
+        let mut pattern = Pattern::from_cells(self.grid.live_cells())?;
+        pattern.set_rule(self.rule);
+        Ok(pattern)
+    }
 
-            if components.len() != 2 {
-                return Err(ConfigError::StartingPointsParsingError);
-            }
+    /// Draws the current game state with the game's [Renderer], which is in-place in the console by default.
+    pub fn print_game_state(&mut self) -> io::Result<()> {
+        self.print_game_state_with_status(&[])
+    }
 
-            let x_component = match components[0].parse::<u8>() {
-                Ok(val) => val,
-                Err(_) => {
-                    return Err(ConfigError::StartingPointsParsingError);
-                }
-            };
-            let y_component = match components[1].parse::<u8>() {
-                Ok(val) => val,
-                Err(_) => {
-                    return Err(ConfigError::StartingPointsParsingError);
-                }
-            };
+    /// Draws the current game state like [print_game_state](Game::print_game_state), followed by some lines of status
+    /// text.
+    pub fn print_game_state_with_status(&mut self, status: &[String]) -> io::Result<()> {
+        let mut footer = Vec::new();
+        if let Some(seed) = self.seed {
+            footer.push(format!("Random seed: {seed}"));
+        }
+        footer.extend_from_slice(status);
 
-            let point = (x_component, y_component);
+        self.fit_viewport(footer.len());
+        if self.viewport.is_following() {
+            self.viewport.follow(self.bounding_box());
+        }
 
-            if starting_cells.contains(&point) {
-                continue;
-            } else {
-                starting_cells.push((x_component, y_component));
-            }
+        let mut lines = self.viewport.lines(&*self.grid);
+        lines.extend(footer);
+        self.draw(&lines)
+    }
+
+    /// Shrinks the view to fit on the renderer's screen above `footer_lines` lines of status text.
+    pub(crate) fn fit_viewport(&mut self, footer_lines: usize) {
+        if let Some((columns, rows)) = self.renderer.size() {
+            let rows = rows as i64 - footer_lines as i64;
+            self.viewport.fit(columns as i64, rows.max(1));
         }
+    }
 
-        Ok(Self {
-            grid_width,
-            grid_height,
-            cycle_count,
-            starting_cells,
-        })
+    /// Draws some lines with the game's [Renderer] in place of the game state.
+    pub fn draw(&mut self, lines: &[String]) -> io::Result<()> {
+        self.renderer.draw(lines)
+    }
+
+    /// Runs one cycle and draws the new game state with the game's [Renderer].
+    ///
+    /// A [JumpTooLarge] error from [tick](Game::tick) is returned as an [io::Error].
+    pub fn step(&mut self) -> io::Result<()> {
+        self.tick().map_err(io::Error::other)?;
+        self.print_game_state()
     }
 
-    pub fn get_x(&self) -> u8 {
-        self.grid_width
+    /// Returns an iterator over the live cells of each generation, starting with the current one, which advances the
+    /// game as it goes with [tick](Game::tick) and ends if it cannot advance any further.
+    ///
+    /// # Example
+    /// ```
+    /// use game_of_life::{Config, Game};
+    ///
+    /// let args = vec!["10", "10", "0", "4,4", "5,4", "6,4"];
+    /// let mut game = Game::new(Config::build(args.into_iter().map(String::from).collect()).unwrap());
+    /// let populations: Vec<(u64, usize)> = game
+    ///     .generations()
+    ///     .take(3)
+    ///     .map(|(generation, cells)| (generation, cells.len()))
+    ///     .collect();
+    ///
+    /// assert_eq!(populations, [(0, 3), (1, 3), (2, 3)]);
+    /// assert_eq!(game.generation(), 2);
+    /// ```
+    pub fn generations(&mut self) -> Generations<'_> {
+        Generations {
+            game: self,
+            started: false,
+        }
     }
+}
+
+/// An iterator over the generations of a [Game], returned by [Game::generations].
+pub struct Generations<'a> {
+    game: &'a mut Game,
+    /// Whether the current generation has been returned, so the next call should advance the game.
+    started: bool,
+}
+
+impl Iterator for Generations<'_> {
+    /// The generation number and the coordinates of its live cells.
+    type Item = (u64, Vec<(i64, i64)>);
 
-    pub fn get_y(&self) -> u8 {
-        self.grid_height
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.started {
+            self.game.tick().ok()?;
+        }
+        self.started = true;
+        Some((self.game.generation(), self.game.grid.live_cells()))
     }
+}
 
-    pub fn get_starting_cells(&self) -> Vec<(u8, u8)> {
-        self.starting_cells.clone()
+/// Creates the universe described by a [Config], seeded with its starting cells.
+fn build_universe(config: &Config) -> Box<dyn Universe> {
+    // Fixed-size engines are limited to 255x255 cells when the config is built
+    let x = config.get_x() as u8;
+    let y = config.get_y() as u8;
+    let starting_cells = config.get_starting_cells();
+    let rule = config.get_rule();
+    let topology = config.get_topology();
+    let threads = config.get_threads();
+    let region = grid_region(config);
+
+    match config.get_engine() {
+        Engine::Grid => Box::new(
+            Grid::new(x, y, to_grid_cells(starting_cells, region), rule, topology)
+                .with_threads(threads),
+        ),
+        Engine::Packed => Box::new(
+            PackedGrid::new(x, y, to_grid_cells(starting_cells, region), rule, topology)
+                .with_threads(threads),
+        ),
+        Engine::Sparse => Box::new(SparseGrid::new(starting_cells, rule)),
+        Engine::HashLife => Box::new(HashLife::new(starting_cells, rule)),
     }
+}
 
-    pub fn get_cycle_count(&self) -> usize {
-        self.cycle_count
+/// Returns the region covered by the grid described by a [Config].
+fn grid_region(config: &Config) -> Region {
+    Region {
+        x: 0,
+        y: 0,
+        width: config.get_x() as i64,
+        height: config.get_y() as i64,
     }
 }
 
+/// Creates the viewport described by a [Config], showing the whole grid unless the terminal is too small.
+fn build_viewport(config: &Config) -> Viewport {
+    let bounded = matches!(config.get_engine(), Engine::Grid | Engine::Packed);
+    let mut viewport = Viewport::new(grid_region(config), bounded);
+    viewport.set_glyphs(config.get_glyphs());
+    viewport.set_colors(config.get_colors());
+    viewport.set_scale(config.get_zoom());
+    viewport.set_following(config.is_following());
+    viewport
+}
+
+/// Returns the cells which lie within a grid covering `bounds`, which are all of them for a validated [Config].
+fn to_grid_cells(cells: Vec<(i64, i64)>, bounds: Region) -> Vec<(u8, u8)> {
+    cells
+        .into_iter()
+        .filter(|&(x, y)| bounds.contains(x, y))
+        .map(|(x, y)| (x as u8, y as u8))
+        .collect()
+}
+
+/// A fixed-size grid of [Cells](Cell).
+///
+/// Grids are equal if they have the same size, rule, topology and cells, regardless of their generation or the number
+/// of threads they use.
 #[derive(Clone)]
 pub struct Grid {
     /// A one-dimensional vector of [Cells](Cell) representing the flattened grid.
     grid: Vec<Cell>,
     width: u8,
     height: u8,
+    rule: Rule,
+    topology: Topology,
+    /// The number of threads used by [step_forward](Grid::step_forward).
+    threads: usize,
+    /// The number of times the grid has been stepped forward.
+    generation: u64,
 }
 
-impl Grid {
-    pub fn new(width: u8, height: u8, starting_cells: Vec<(u8, u8)>) -> Self {
-        let mut grid: Vec<Cell> = Vec::new();
-
-        for b in 0..height {
-            for a in 0..width {
-                let cell = Cell::new(a, b, starting_cells.contains(&(a, b)));
-                grid.push(cell);
-            }
-        }
+impl PartialEq for Grid {
+    fn eq(&self, other: &Self) -> bool {
+        self.width == other.width
+            && self.height == other.height
+            && self.rule == other.rule
+            && self.topology == other.topology
+            && self.grid == other.grid
+    }
+}
 
-        Self {
-            grid,
+impl Grid {
+    /// Creates a grid with the given cells alive, ignoring any outside the grid.
+    ///
+    /// # Example
+    /// A glider on a torus returns to its starting position after `4 * width` generations:
+    /// ```
+    /// use game_of_life::{Grid, Rule, Topology};
+    ///
+    /// let glider = vec![(1, 2), (2, 1), (0, 0), (1, 0), (2, 0)];
+    /// let start = Grid::new(8, 8, glider, Rule::CONWAY, Topology::Torus);
+    ///
+    /// let mut grid = start.clone();
+    /// for _ in 0..4 * 8 {
+    ///     grid.step_forward();
+    /// }
+    /// assert!(grid == start);
+    /// assert_eq!(grid.generation(), 4 * 8);
+    /// ```
+    pub fn new(
+        width: u8,
+        height: u8,
+        starting_cells: Vec<(u8, u8)>,
+        rule: Rule,
+        topology: Topology,
+    ) -> Self {
+        let mut grid = Self {
+            grid: (0..height)
+                .flat_map(|b| (0..width).map(move |a| Cell::new(a, b, false)))
+                .collect(),
             width,
             height,
+            rule,
+            topology,
+            threads: 1,
+            generation: 0,
+        };
+
+        let region = grid.region();
+        for (x, y) in starting_cells {
+            if region.contains(x as i64, y as i64) {
+                grid.grid[y as usize * width as usize + x as usize].set_alive(true);
+            }
         }
+        grid
+    }
+
+    /// Sets the number of threads used to step the grid, where `0` uses every available core.
+    ///
+    /// The grid is split into horizontal bands which are updated in parallel, with identical results for any number
+    /// of threads.
+    ///
+    /// # Example
+    /// ```
+    /// use game_of_life::{Grid, Rule, Topology};
+    ///
+    /// let blinker = vec![(0, 1), (1, 1), (2, 1)];
+    /// let mut grid = Grid::new(40, 40, blinker, Rule::CONWAY, Topology::Torus).with_threads(4);
+    /// grid.step_forward();
+    /// assert!(grid.get_cell(1, 2).unwrap().is_alive());
+    /// ```
+    pub fn with_threads(mut self, threads: usize) -> Self {
+        self.threads = resolve_thread_count(threads);
+        self
     }
 
     /// Returns the cell at the corresponding coordinates or `None` if the coordinates point outside the grid.
-    fn get_cell(&self, x: i16, y: i16) -> Option<&Cell> {
+    ///
+    /// Coordinates on a wrapping edge are wrapped around to the opposite side first.
+    fn get_neighbour(&self, x: i16, y: i16) -> Option<&Cell> {
         let width = self.width as i16;
         let height = self.height as i16;
-        if x < 0 || x >= width || y < 0 || y >= height {
-            None
+        let x = if self.topology.wraps_horizontally() {
+            x.rem_euclid(width)
+        } else {
+            x
+        };
+        let y = if self.topology.wraps_vertically() {
+            y.rem_euclid(height)
         } else {
-            Some(&self.grid[(y * width + x) as usize])
+            y
+        };
+        self.region()
+            .contains(x as i64, y as i64)
+            .then(|| &self.grid[y as usize * width as usize + x as usize])
+    }
+
+    /// Returns `true` if the cell at the given coordinates is alive, or a [CoordinateError] if they point outside the
+    /// grid.
+    ///
+    /// # Example
+    /// ```
+    /// use game_of_life::{Grid, Rule, Topology, Universe};
+    ///
+    /// let grid = Grid::new(3, 3, vec![(1, 2)], Rule::CONWAY, Topology::Bounded);
+    /// assert_eq!(grid.is_alive(1, 2), Ok(true));
+    /// assert_eq!(grid.is_alive(0, 0), Ok(false));
+    /// assert!(grid.is_alive(3, 0).is_err());
+    ///
+    /// // The `Universe` method is total, treating cells outside the grid as dead
+    /// assert!(!Universe::is_alive(&grid, 3, 0));
+    /// ```
+    pub fn is_alive(&self, x: i64, y: i64) -> Result<bool, CoordinateError> {
+        CoordinateError::check(x, y, self.region())?;
+        Ok(self.grid[y as usize * self.width as usize + x as usize].is_alive())
+    }
+
+    /// Returns the cell at the given coordinates, or a [CoordinateError] if they point outside the grid.
+    pub fn get_cell(&self, x: u8, y: u8) -> Result<&Cell, CoordinateError> {
+        CoordinateError::check(x as i64, y as i64, self.region())?;
+        Ok(&self.grid[y as usize * self.width as usize + x as usize])
+    }
+
+    /// Makes the cell at the given coordinates alive or dead, or returns a [CoordinateError] if they point outside the
+    /// grid.
+    ///
+    /// A cell which changes state has its age reset, as though it had just been born or died.
+    ///
+    /// # Example
+    /// ```
+    /// use game_of_life::{Grid, Rule, Topology, Universe};
+    ///
+    /// let mut grid = Grid::new(3, 3, vec![], Rule::CONWAY, Topology::Bounded);
+    /// grid.set(1, 2, true).unwrap();
+    /// assert!(grid.get_cell(1, 2).unwrap().is_alive());
+    /// assert_eq!(grid.population(), 1);
+    ///
+    /// let error = grid.set(3, 0, true).unwrap_err();
+    /// assert_eq!(error.to_string(), "Cell 3,0 is outside the 3x3 grid.");
+    /// ```
+    pub fn set(&mut self, x: u8, y: u8, alive: bool) -> Result<(), CoordinateError> {
+        CoordinateError::check(x as i64, y as i64, self.region())?;
+        self.grid[y as usize * self.width as usize + x as usize].set_alive(alive);
+        Ok(())
+    }
+
+    pub fn width(&self) -> u8 {
+        self.width
+    }
+
+    pub fn height(&self) -> u8 {
+        self.height
+    }
+
+    /// Returns the number of times the grid has been [stepped forward](Grid::step_forward).
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    /// Returns the region covered by the grid.
+    fn region(&self) -> Region {
+        Region {
+            x: 0,
+            y: 0,
+            width: self.width as i64,
+            height: self.height as i64,
         }
     }
 
-    /// Updates each cell in the grid according to Conway's rules
+    /// Updates each cell in the grid according to the grid's [Rule]
     pub fn step_forward(&mut self) {
         let initial_grid_state = self.clone();
+        self.generation += 1;
 
-        for cell in &mut self.grid {
-            let (x, y) = cell.get_coords();
-            let (x, y) = (x as i16, y as i16);
+        if self.threads <= 1 {
+            for cell in &mut self.grid {
+                initial_grid_state.update_cell(cell);
+            }
+            return;
+        }
 
-            let mut neighbours = Vec::new();
+        let band_length = rows_per_band(self.height as usize, self.threads) * self.width as usize;
 
-            for &i in &[x - 1, x, x + 1] {
-                for &j in &[y - 1, y, y + 1] {
-                    if i == x && j == y {
-                        continue;
+        thread::scope(|scope| {
+            for band in self.grid.chunks_mut(band_length.max(1)) {
+                let initial_grid_state = &initial_grid_state;
+                scope.spawn(move || {
+                    for cell in band {
+                        initial_grid_state.update_cell(cell);
                     }
-                    neighbours.push(initial_grid_state.get_cell(i, j));
-                }
+                });
             }
-
-            cell.update_state(neighbours);
-        }
+        });
     }
 
-    /// Prints the grid in-place in the console.
-    pub fn print_grid(&self) {
-        let mut line: Vec<&Cell> = Vec::new();
+    /// Updates a cell according to its neighbours in this grid.
+    fn update_cell(&self, cell: &mut Cell) {
+        let (x, y) = cell.get_coords();
+        let (x, y) = (x as i16, y as i16);
 
-        std::process::Command::new("clear")
-            .status()
-            .expect("Could not run command \"clear\"");
+        let mut neighbours = Vec::new();
 
-        for i in (0..(self.width * self.height)).rev() {
-            if i > self.width * self.height - self.width - 1 {
-                line.insert(0, &self.grid[i as usize])
-            } else {
-                line[(i % self.width) as usize] = &self.grid[i as usize];
-            }
-            if i % self.width == 0 {
-                for &e in &line {
-                    print!("{:?} ", e);
+        for &i in &[x - 1, x, x + 1] {
+            for &j in &[y - 1, y, y + 1] {
+                if i == x && j == y {
+                    continue;
                 }
-                println!();
+                neighbours.push(self.get_neighbour(i, j));
             }
         }
+
+        cell.update_state(neighbours, &self.rule);
     }
 }
 
-#[derive(Clone, PartialEq)]
+impl Universe for Grid {
+    fn step_forward(&mut self) {
+        Grid::step_forward(self);
+    }
+
+    /// Returns `true` if the cell at the given coordinates is alive, or `false` if they point outside the grid.
+    ///
+    /// Unlike the fallible inherent [is_alive](Grid::is_alive), this is total, so that every [Universe] can be queried
+    /// in the same way.
+    fn is_alive(&self, x: i64, y: i64) -> bool {
+        Grid::is_alive(self, x, y).unwrap_or(false)
+    }
+
+    fn set_alive(&mut self, x: i64, y: i64, alive: bool) -> Result<(), CoordinateError> {
+        CoordinateError::check(x, y, self.region())?;
+        self.set(x as u8, y as u8, alive)
+    }
+
+    fn bounds(&self) -> Option<Region> {
+        Some(self.region())
+    }
+
+    fn cell_age(&self, x: i64, y: i64) -> Option<CellAge> {
+        let x = u8::try_from(x).ok()?;
+        let y = u8::try_from(y).ok()?;
+        self.get_cell(x, y).ok().map(Cell::get_age)
+    }
+
+    fn live_cells(&self) -> Vec<(i64, i64)> {
+        self.grid
+            .iter()
+            .filter(|cell| cell.state == State::Alive)
+            .map(|cell| (cell.x as i64, cell.y as i64))
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum State {
     Alive = 1,
     Dead = 0,
 }
 
+/// A cell of a [Grid], which tracks how long it has been in its current state.
+///
+/// Cells are equal if they have the same coordinates and state, regardless of their ages.
 #[derive(Clone)]
 pub struct Cell {
     state: State,
     x: u8,
     y: u8,
+    /// The number of generations the cell has been in its current state
+    age: u32,
+    /// Whether the cell has ever been alive
+    has_lived: bool,
+}
+
+impl PartialEq for Cell {
+    fn eq(&self, other: &Self) -> bool {
+        self.state == other.state && self.x == other.x && self.y == other.y
+    }
 }
 
 impl fmt::Debug for Cell {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(
-            f,
-            "{}",
-            if self.state.clone() == State::Alive {
-                'O'
-            } else {
-                '.'
-            }
-        )
+        write!(f, "{}", if self.state == State::Alive { 'O' } else { '.' })
     }
 }
 
@@ -289,38 +765,151 @@ impl Cell {
             state: if alive { State::Alive } else { State::Dead },
             x,
             y,
+            age: 0,
+            has_lived: alive,
         }
     }
 
-    pub fn update_state(&mut self, neighbours: Vec<Option<&Cell>>) {
-        self.state = calc_new_state(self.state.clone(), neighbours);
+    pub fn update_state(&mut self, neighbours: Vec<Option<&Cell>>, rule: &Rule) {
+        let new_state = calc_new_state(self.state, neighbours, rule);
+
+        if new_state == self.state {
+            self.age = self.age.saturating_add(1);
+        } else {
+            self.age = 0;
+        }
+        if new_state == State::Alive {
+            self.has_lived = true;
+        }
+        self.state = new_state;
     }
 
     pub fn get_coords(&self) -> (u8, u8) {
         (self.x, self.y)
     }
+
+    pub fn get_state(&self) -> State {
+        self.state
+    }
+
+    pub fn is_alive(&self) -> bool {
+        self.state == State::Alive
+    }
+
+    /// Makes the cell alive or dead, resetting its age if its state changes.
+    fn set_alive(&mut self, alive: bool) {
+        let state = if alive { State::Alive } else { State::Dead };
+        if state != self.state {
+            self.state = state;
+            self.age = 0;
+        }
+        self.has_lived |= alive;
+    }
+
+    /// Returns how long the cell has been alive, or how long ago it died.
+    ///
+    /// # Example
+    /// The middle of a blinker stays alive while its ends die and are born again:
+    /// ```
+    /// use game_of_life::{CellAge, Grid, Rule, Topology};
+    ///
+    /// let mut grid = Grid::new(5, 5, vec![(1, 2), (2, 2), (3, 2)], Rule::CONWAY, Topology::Bounded);
+    /// grid.step_forward();
+    /// assert_eq!(grid.get_cell(2, 2).unwrap().get_age(), CellAge::Alive(1));
+    /// assert_eq!(grid.get_cell(1, 2).unwrap().get_age(), CellAge::Dead(0));
+    /// assert_eq!(grid.get_cell(2, 3).unwrap().get_age(), CellAge::Alive(0));
+    /// assert_eq!(grid.get_cell(0, 0).unwrap().get_age(), CellAge::NeverAlive);
+    ///
+    /// grid.step_forward();
+    /// assert_eq!(grid.get_cell(2, 2).unwrap().get_age(), CellAge::Alive(2));
+    /// assert_eq!(grid.get_cell(2, 3).unwrap().get_age(), CellAge::Dead(0));
+    /// ```
+    pub fn get_age(&self) -> CellAge {
+        match self.state {
+            State::Alive => CellAge::Alive(self.age),
+            State::Dead if self.has_lived => CellAge::Dead(self.age),
+            State::Dead => CellAge::NeverAlive,
+        }
+    }
 }
 
-fn calc_new_state(current_state: State, neighbours: Vec<Option<&Cell>>) -> State {
+fn calc_new_state(current_state: State, neighbours: Vec<Option<&Cell>>, rule: &Rule) -> State {
     let live_neighbour_count: u8 = neighbours
         .iter()
         .map(|&cell| unwrap_cell_state_value(cell))
         .reduce(|acc, curr| acc + curr)
         .unwrap_or_default();
 
-    #[allow(clippy::if_same_then_else)]
-    if current_state == State::Alive && (live_neighbour_count == 2 || live_neighbour_count == 3) {
-        State::Alive
-    } else if current_state == State::Dead && live_neighbour_count == 3 {
-        State::Alive
-    } else {
-        State::Dead
-    }
+    rule.next_state(current_state, live_neighbour_count)
 }
 
 fn unwrap_cell_state_value(cell_option: Option<&Cell>) -> u8 {
     match cell_option {
-        Some(cell) => cell.state.clone() as u8,
+        Some(cell) => cell.state as u8,
         None => 0,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{fixture_cells, TOPOLOGIES};
+    use std::{cell::RefCell, rc::Rc};
+
+    /// A recorder which keeps the number of each generation it is given.
+    struct Generations(Rc<RefCell<Vec<u64>>>);
+
+    impl Recorder for Generations {
+        fn add_frame(&mut self, _universe: &dyn Universe, generation: u64) -> io::Result<()> {
+            self.0.borrow_mut().push(generation);
+            Ok(())
+        }
+
+        fn finish(self: Box<Self>) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn advancing_records_nothing() {
+        let args = ["5", "5", "0", "1,2", "2,2", "3,2"];
+        let mut game = Game::new(Config::build(args.map(String::from).to_vec()).unwrap());
+        let frames = Rc::new(RefCell::new(Vec::new()));
+        let mut recording = Recording::new();
+        recording.add(Box::new(Generations(Rc::clone(&frames))), &game);
+
+        game.tick().unwrap();
+        game.advance(2).unwrap();
+        game.generations().take(3).for_each(drop);
+        assert_eq!(*frames.borrow(), [0]);
+
+        recording.add_frame(&game);
+        game.advance(1).unwrap();
+        recording.add_frame(&game);
+        assert_eq!(*frames.borrow(), [0, 5, 6]);
+        recording.finish().unwrap();
+    }
+
+    #[test]
+    fn grid_threads_give_identical_results() {
+        for topology in TOPOLOGIES {
+            let cells = fixture_cells(40, 40);
+            let mut expected = Grid::new(40, 40, cells.clone(), Rule::CONWAY, topology);
+            for _ in 0..10 {
+                expected.step_forward();
+            }
+
+            for threads in [0, 2, 3, 7, 64] {
+                let mut grid =
+                    Grid::new(40, 40, cells.clone(), Rule::CONWAY, topology).with_threads(threads);
+                for _ in 0..10 {
+                    grid.step_forward();
+                }
+                assert!(
+                    grid.with_threads(1) == expected,
+                    "{threads} threads on {topology:?}"
+                );
+            }
+        }
+    }
+}
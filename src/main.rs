@@ -1,15 +1,56 @@
-use game_of_life::{Config, Game};
-use std::{env, error::Error, process, thread, time};
+use game_of_life::{
+    completions, version, Command, Config, Game, GifRecorder, Pattern, PatternFormat, Recording,
+    Search, StatsRecorder, SvgSheet, Tui,
+};
+use std::{
+    env,
+    error::Error,
+    fs,
+    path::Path,
+    process,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    thread, time,
+};
+
+/// The most generations run by `analyze` when no generation count is given.
+const ANALYZE_GENERATIONS: u64 = 1000;
 
 fn main() {
     let args: Vec<String> = env::args().skip(1).collect();
 
-    let config = Config::build(args).unwrap_or_else(|err| {
+    let command = Command::parse(args).unwrap_or_else(|err| {
         println!("Problem parsing arguments: {err}");
-        process::exit(1);
+        println!("Run \"{} --help\" for usage.", env!("CARGO_PKG_NAME"));
+        process::exit(err.exit_code());
     });
 
-    if let Err(err) = run(config) {
+    let result = match command {
+        Command::Run(config) => run(config),
+        Command::Analyze(config) => analyze(config),
+        Command::Convert {
+            input,
+            output,
+            format,
+        } => convert(&input, &output, format),
+        Command::Search(search) => run_search(&search),
+        Command::Completions(shell) => {
+            print!("{}", completions(shell));
+            Ok(())
+        }
+        Command::Help(text) => {
+            print!("{text}");
+            Ok(())
+        }
+        Command::Version => {
+            println!("{}", version());
+            Ok(())
+        }
+    };
+
+    if let Err(err) = result {
         println!("Application error: {err}");
         process::exit(1);
     }
@@ -17,14 +58,216 @@ fn main() {
 
 fn run(config: Config) -> Result<(), Box<dyn Error>> {
     let cycle_count = config.get_cycle_count();
+    let delay = time::Duration::from_millis(config.get_delay());
+    let save_path = config.get_save_path();
+    let seed = config.get_seed();
+    let headless = config.is_headless();
+    let output_format = config.get_output_format();
+    let interactive = config.is_interactive();
+    let stop_when_settled = config.stops_when_settled();
+    let editing = config.is_editing();
+    let png_path = config.get_png_path();
+    let image_style = config.get_image_style();
+    let gif_path = config.get_gif_path();
+    let frame_delay = config.get_frame_delay();
+    let svg_path = config.get_svg_path();
+    let svg_style = config.get_svg_style();
+    let svg_sheet_path = config.get_svg_sheet_path();
+    let sheet_frames = config.get_sheet_frames();
+    let sheet_columns = config.get_sheet_columns();
+    let stats_path = config.get_stats_path();
+    let stats_format = config.get_stats_format();
+    let mut game = Game::new(config);
+    if stop_when_settled {
+        game.detect_periods();
+    }
+
+    let mut recording = Recording::new();
+    if let Some(path) = gif_path {
+        let recorder = GifRecorder::create(path, image_style, game.grid_region(), frame_delay)?;
+        recording.add(Box::new(recorder), &game);
+    }
+    if let Some(path) = svg_sheet_path {
+        let sheet = SvgSheet::new(svg_style, game.grid_region(), sheet_columns)
+            .with_max_frames(sheet_frames)
+            .with_path(path);
+        recording.add(Box::new(sheet), &game);
+    }
+    if let Some(path) = stats_path {
+        let recorder = StatsRecorder::create_with_format(path, stats_format)?;
+        recording.add(Box::new(recorder), &game);
+    }
+
+    if headless {
+        run_headless(
+            &mut game,
+            &mut recording,
+            cycle_count,
+            stop_when_settled,
+            output_format,
+            seed,
+        )?;
+    } else if interactive {
+        let mut tui = Tui::new(&mut game, cycle_count as u64)
+            .with_delay(delay)
+            .with_recording(&mut recording);
+        if editing {
+            tui = tui.editing();
+        }
+        if stop_when_settled {
+            tui = tui.pausing_when_settled();
+        }
+        tui.run()?;
+    } else {
+        let running = Arc::new(AtomicBool::new(true));
+        let handler_running = Arc::clone(&running);
+        ctrlc::set_handler(move || handler_running.store(false, Ordering::SeqCst))?;
+
+        game.print_game_state()?;
+        for _ in get_cycle_range(cycle_count) {
+            thread::sleep(delay);
+            if !running.load(Ordering::SeqCst) {
+                break;
+            }
+            game.step()?;
+            recording.add_frame(&game);
+            if stop_when_settled && game.stability().is_some() {
+                break;
+            }
+        }
+        if let Some(stability) = game.stability() {
+            game.print_game_state_with_status(&[format!(
+                "Settled at generation {}: {stability}",
+                game.generation()
+            )])?;
+        }
+    }
+
+    recording.finish()?;
+    if let Some(path) = save_path {
+        game.to_pattern()?.save(path)?;
+    }
+    if let Some(path) = png_path {
+        game.to_image(&image_style)?.save_png(path)?;
+    }
+    if let Some(path) = svg_path {
+        fs::write(path, game.to_svg(&svg_style))?;
+    }
+    Ok(())
+}
+
+/// Runs `cycle_count` cycles without drawing, or until the pattern settles if `stop_when_settled` is `true`, adding
+/// each generation to `recording`, then prints the final state to stdout and statistics to stderr.
+fn run_headless(
+    game: &mut Game,
+    recording: &mut Recording,
+    cycle_count: usize,
+    stop_when_settled: bool,
+    format: PatternFormat,
+    seed: Option<u64>,
+) -> Result<(), Box<dyn Error>> {
+    let start = time::Instant::now();
+    if stop_when_settled || !recording.is_empty() {
+        for _ in get_cycle_range(cycle_count) {
+            if stop_when_settled && game.stability().is_some() {
+                break;
+            }
+            game.advance(1)?;
+            recording.add_frame(game);
+        }
+    } else {
+        game.advance(cycle_count as u64)?;
+    }
+    let elapsed = start.elapsed();
+
+    print!("{}", game.to_pattern()?.write(format));
+
+    eprintln!("Generations: {}", game.generation());
+    eprintln!("Population: {}", game.population());
+    match game.bounding_box() {
+        Some(bounds) => eprintln!(
+            "Bounding box: {}x{} from {},{}",
+            bounds.width, bounds.height, bounds.x, bounds.y
+        ),
+        None => eprintln!("Bounding box: empty"),
+    }
+    if stop_when_settled {
+        match game.stability() {
+            Some(stability) => eprintln!("Stability: {stability}"),
+            None => eprintln!("Stability: not settled"),
+        }
+    }
+    eprintln!("Elapsed time: {elapsed:?}");
+    if let Some(seed) = seed {
+        eprintln!("Random seed: {seed}");
+    }
+    Ok(())
+}
+
+/// Runs a pattern until it settles or its cycle count, or [ANALYZE_GENERATIONS] if it has none, is reached, then
+/// prints how it behaves.
+fn analyze(config: Config) -> Result<(), Box<dyn Error>> {
+    let cycle_count = match config.get_cycle_count() {
+        0 => ANALYZE_GENERATIONS,
+        cycle_count => cycle_count as u64,
+    };
     let mut game = Game::new(config);
+    let initial_population = game.population();
+    game.detect_periods();
+    while game.stability().is_none() && game.generation() < cycle_count {
+        game.advance(1)?;
+    }
+
+    println!("Initial population: {initial_population}");
+    println!("Generations: {}", game.generation());
+    println!("Population: {}", game.population());
+    match game.bounding_box() {
+        Some(bounds) => println!(
+            "Bounding box: {}x{} from {},{}",
+            bounds.width, bounds.height, bounds.x, bounds.y
+        ),
+        None => println!("Bounding box: empty"),
+    }
+    match game.stability() {
+        Some(stability) => println!("Stability: {stability}"),
+        None => println!("Stability: not settled after {cycle_count} generations"),
+    }
+    Ok(())
+}
+
+/// Converts a pattern file to `format`, or the format chosen by the output's extension, printing it if the output is
+/// `-`.
+fn convert(
+    input: &Path,
+    output: &Path,
+    format: Option<PatternFormat>,
+) -> Result<(), Box<dyn Error>> {
+    let pattern = Pattern::load(input)?;
+    match format {
+        _ if output == Path::new("-") => {
+            print!("{}", pattern.write(format.unwrap_or(PatternFormat::Rle)))
+        }
+        Some(format) => fs::write(output, pattern.write(format))?,
+        None => pattern.save(output)?,
+    }
+    Ok(())
+}
 
-    game.print_game_state();
-    for _ in get_cycle_range(cycle_count) {
-        let period = time::Duration::from_millis(100);
-        thread::sleep(period);
-        game.step();
+/// Prints each soup which matches the search's target, followed by how many were found.
+fn run_search(search: &Search) -> Result<(), Box<dyn Error>> {
+    let mut found = 0;
+    for result in search.results() {
+        let outcome = match result.stability {
+            Some(stability) => stability.to_string(),
+            None => String::from("not settled"),
+        };
+        println!(
+            "Seed {}: {outcome} at generation {}, population {}",
+            result.seed, result.generations, result.population
+        );
+        found += 1;
     }
+    eprintln!("Found {found} soups matching \"{}\".", search.target());
     Ok(())
 }
 
@@ -0,0 +1,328 @@
+use std::thread;
+
+use crate::{
+    universe::{resolve_thread_count, rows_per_band},
+    CoordinateError, Region, Rule, Topology, Universe,
+};
+
+/// The most words a row can need, since grids are at most 255 cells wide.
+const MAX_ROW_WORDS: usize = 4;
+
+/// A fixed-size grid storing each row as bits, 64 cells per word.
+///
+/// Neighbours are counted for 64 cells at a time with bitwise adders, and the next generation is written into a
+/// second buffer which is swapped in afterwards, so stepping does not allocate.
+#[derive(Clone, PartialEq)]
+pub struct PackedGrid {
+    /// The rows of the grid from bottom to top, each `row_words` long. Bit `i` of word `k` is the cell at `x = 64k + i`.
+    cells: Vec<u64>,
+    /// The buffer the next generation is written into.
+    next: Vec<u64>,
+    row_words: usize,
+    width: u8,
+    height: u8,
+    rule: Rule,
+    topology: Topology,
+    /// The number of threads used by [step_forward](Universe::step_forward).
+    threads: usize,
+}
+
+impl PackedGrid {
+    /// Creates a packed grid with the given cells alive, ignoring any outside the grid.
+    ///
+    /// # Example
+    /// A packed grid evolves identically to a [Grid](crate::Grid), while storing each cell in a single bit:
+    /// ```
+    /// use game_of_life::{PackedGrid, Rule, Topology, Universe};
+    ///
+    /// let glider = vec![(1, 12), (2, 11), (0, 10), (1, 10), (2, 10)];
+    /// let mut packed = PackedGrid::new(100, 30, glider, Rule::CONWAY, Topology::Torus);
+    /// packed.advance(4).unwrap();
+    /// assert!(packed.is_alive(3, 10) && packed.is_alive(1, 9));
+    /// assert_eq!(packed.population(), 5);
+    /// ```
+    pub fn new(
+        width: u8,
+        height: u8,
+        starting_cells: Vec<(u8, u8)>,
+        rule: Rule,
+        topology: Topology,
+    ) -> Self {
+        let row_words = (width as usize).div_ceil(64);
+        let mut cells = vec![0; row_words * height as usize];
+
+        for (x, y) in starting_cells {
+            if x < width && y < height {
+                cells[y as usize * row_words + x as usize / 64] |= 1 << (x % 64);
+            }
+        }
+
+        Self {
+            next: cells.clone(),
+            cells,
+            row_words,
+            width,
+            height,
+            rule,
+            topology,
+            threads: 1,
+        }
+    }
+
+    /// Sets the number of threads used to step the grid, where `0` uses every available core.
+    ///
+    /// # Example
+    /// ```
+    /// use game_of_life::{PackedGrid, Rule, Topology, Universe};
+    ///
+    /// let blinker = vec![(0, 1), (1, 1), (2, 1)];
+    /// let mut grid = PackedGrid::new(200, 50, blinker, Rule::CONWAY, Topology::Torus).with_threads(0);
+    /// grid.advance(1).unwrap();
+    /// assert!(grid.is_alive(1, 0) && grid.is_alive(1, 2));
+    /// ```
+    pub fn with_threads(mut self, threads: usize) -> Self {
+        self.threads = resolve_thread_count(threads);
+        self
+    }
+
+    /// Returns the row at `y`, wrapping or returning `None` beyond the top and bottom edges.
+    fn row(&self, y: isize) -> Option<&[u64]> {
+        let height = self.height as isize;
+        let y = if self.topology.wraps_vertically() {
+            y.rem_euclid(height)
+        } else if y < 0 || y >= height {
+            return None;
+        } else {
+            y
+        };
+        let start = y as usize * self.row_words;
+        Some(&self.cells[start..start + self.row_words])
+    }
+
+    /// Returns the row shifted so that each bit holds its western (`x - 1`) and eastern (`x + 1`) neighbours.
+    fn shifted(&self, row: &[u64]) -> ([u64; MAX_ROW_WORDS], [u64; MAX_ROW_WORDS]) {
+        let mut west = [0; MAX_ROW_WORDS];
+        let mut east = [0; MAX_ROW_WORDS];
+        let last = self.row_words - 1;
+        let last_bit = (self.width as usize - 1) % 64;
+
+        for k in 0..self.row_words {
+            west[k] = row[k] << 1;
+            east[k] = row[k] >> 1;
+            if k > 0 {
+                west[k] |= row[k - 1] >> 63;
+            }
+            if k < last {
+                east[k] |= row[k + 1] << 63;
+            }
+        }
+
+        if self.topology.wraps_horizontally() {
+            west[0] |= (row[last] >> last_bit) & 1;
+            east[last] |= (row[0] & 1) << last_bit;
+        }
+        west[last] &= last_word_mask(self.width);
+
+        (west, east)
+    }
+
+    /// Writes the next generation of the rows starting at `first_row` into `output`, which holds whole rows.
+    fn step_rows(&self, first_row: usize, output: &mut [u64]) {
+        let empty = [0; MAX_ROW_WORDS];
+
+        for (offset, next_row) in output.chunks_mut(self.row_words).enumerate() {
+            let y = (first_row + offset) as isize;
+            let below = self.row(y - 1).unwrap_or(&empty[..self.row_words]);
+            let current = self.row(y).unwrap();
+            let above = self.row(y + 1).unwrap_or(&empty[..self.row_words]);
+
+            let (below_west, below_east) = self.shifted(below);
+            let (west, east) = self.shifted(current);
+            let (above_west, above_east) = self.shifted(above);
+
+            for (k, next_word) in next_row.iter_mut().enumerate() {
+                let mut counters = [0; 4];
+                for neighbours in [
+                    below_west[k],
+                    below[k],
+                    below_east[k],
+                    west[k],
+                    east[k],
+                    above_west[k],
+                    above[k],
+                    above_east[k],
+                ] {
+                    add_bit(&mut counters, neighbours);
+                }
+
+                let alive = current[k];
+                let mut next = 0;
+                for n in 0..=8 {
+                    let equal = counters_equal(&counters, n);
+                    if self.rule.survives(n) {
+                        next |= alive & equal;
+                    }
+                    if self.rule.is_born(n) {
+                        next |= !alive & equal;
+                    }
+                }
+                if k == self.row_words - 1 {
+                    next &= last_word_mask(self.width);
+                }
+
+                *next_word = next;
+            }
+        }
+    }
+}
+
+/// Returns a mask of the valid cells in the last word of a row.
+fn last_word_mask(width: u8) -> u64 {
+    match width % 64 {
+        0 => u64::MAX,
+        bits => (1 << bits) - 1,
+    }
+}
+
+/// Adds one bit to each of 64 bit-sliced 4-bit counters.
+fn add_bit(counters: &mut [u64; 4], bit: u64) {
+    let carry0 = counters[0] & bit;
+    counters[0] ^= bit;
+    let carry1 = counters[1] & carry0;
+    counters[1] ^= carry0;
+    let carry2 = counters[2] & carry1;
+    counters[2] ^= carry1;
+    counters[3] |= carry2;
+}
+
+/// Returns a mask of the counters equal to `n`.
+fn counters_equal(counters: &[u64; 4], n: u8) -> u64 {
+    (0..4).fold(u64::MAX, |mask, bit| {
+        mask & if n & (1 << bit) != 0 {
+            counters[bit]
+        } else {
+            !counters[bit]
+        }
+    })
+}
+
+impl Universe for PackedGrid {
+    /// Updates each cell in the grid according to the grid's [Rule].
+    fn step_forward(&mut self) {
+        if self.row_words == 0 {
+            return;
+        }
+        let mut next_cells = std::mem::take(&mut self.next);
+
+        if self.threads <= 1 {
+            self.step_rows(0, &mut next_cells);
+        } else {
+            let band_length = rows_per_band(self.height as usize, self.threads) * self.row_words;
+            let grid = &*self;
+
+            thread::scope(|scope| {
+                for (index, band) in next_cells.chunks_mut(band_length).enumerate() {
+                    scope.spawn(move || grid.step_rows(index * band_length / grid.row_words, band));
+                }
+            });
+        }
+
+        self.next = std::mem::replace(&mut self.cells, next_cells);
+    }
+
+    fn is_alive(&self, x: i64, y: i64) -> bool {
+        if !self.bounds().unwrap().contains(x, y) {
+            return false;
+        }
+        let word = self.cells[y as usize * self.row_words + x as usize / 64];
+        word & (1 << (x % 64)) != 0
+    }
+
+    fn set_alive(&mut self, x: i64, y: i64, alive: bool) -> Result<(), CoordinateError> {
+        CoordinateError::check(x, y, self.bounds().unwrap())?;
+        let word = &mut self.cells[y as usize * self.row_words + x as usize / 64];
+        if alive {
+            *word |= 1 << (x % 64);
+        } else {
+            *word &= !(1 << (x % 64));
+        }
+        Ok(())
+    }
+
+    fn bounds(&self) -> Option<Region> {
+        Some(Region {
+            x: 0,
+            y: 0,
+            width: self.width as i64,
+            height: self.height as i64,
+        })
+    }
+
+    fn population(&self) -> u64 {
+        self.cells.iter().map(|word| word.count_ones() as u64).sum()
+    }
+
+    fn live_cells(&self) -> Vec<(i64, i64)> {
+        let mut cells = Vec::new();
+        for (index, &word) in self.cells.iter().enumerate() {
+            let y = (index / self.row_words) as i64;
+            let x_offset = (index % self.row_words) as i64 * 64;
+            let mut word = word;
+            while word != 0 {
+                cells.push((x_offset + word.trailing_zeros() as i64, y));
+                word &= word - 1;
+            }
+        }
+        cells
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        testing::{fixture_cells, TOPOLOGIES},
+        Grid,
+    };
+
+    #[test]
+    fn packed_grid_matches_grid() {
+        let rule: Rule = "B36/S23".parse().unwrap();
+        let cells = fixture_cells(100, 30);
+
+        for topology in TOPOLOGIES {
+            let mut grid = Grid::new(100, 30, cells.clone(), rule, topology);
+            let mut packed = PackedGrid::new(100, 30, cells.clone(), rule, topology);
+            for _ in 0..20 {
+                grid.step_forward();
+                Universe::step_forward(&mut packed);
+            }
+
+            let (mut expected, mut actual) = (grid.live_cells(), packed.live_cells());
+            expected.sort();
+            actual.sort();
+            assert_eq!(actual, expected, "{topology:?}");
+        }
+    }
+
+    #[test]
+    fn packed_threads_give_identical_results() {
+        let cells = fixture_cells(200, 50);
+
+        for topology in TOPOLOGIES {
+            let mut expected = PackedGrid::new(200, 50, cells.clone(), Rule::CONWAY, topology);
+            expected.advance(30).unwrap();
+
+            for threads in [0, 2, 3, 7, 64] {
+                let mut grid = PackedGrid::new(200, 50, cells.clone(), Rule::CONWAY, topology)
+                    .with_threads(threads);
+                grid.advance(30).unwrap();
+                assert_eq!(
+                    grid.live_cells(),
+                    expected.live_cells(),
+                    "{threads} threads on {topology:?}"
+                );
+            }
+        }
+    }
+}
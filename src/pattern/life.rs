@@ -0,0 +1,178 @@
+use super::{Pattern, PatternError};
+use crate::Rule;
+
+/// Parses a pattern in the Life 1.05 format, made up of `#P x y` blocks of `.` and `*` rows.
+///
+/// Block positions, like the rows within them, count downwards.
+pub(super) fn parse_105(input: &str) -> Result<Pattern, PatternError> {
+    let mut comments = Vec::new();
+    let mut rule = None;
+    let mut cells = Vec::new();
+    let mut block = (0, 0);
+    let mut row = 0;
+
+    for (index, line) in input.lines().enumerate() {
+        let line_number = index + 1;
+        let line = line.trim();
+
+        if line.is_empty() || line.starts_with("#Life") {
+            continue;
+        } else if let Some(comment) = line.strip_prefix("#D") {
+            comments.push(comment.trim().to_string());
+        } else if line == "#N" {
+            rule = Some(Rule::CONWAY);
+        } else if let Some(rulestring) = line.strip_prefix("#R") {
+            rule = Some(
+                rulestring
+                    .trim()
+                    .parse()
+                    .map_err(|error| PatternError::InvalidRule {
+                        line: line_number,
+                        error,
+                    })?,
+            );
+        } else if let Some(position) = line.strip_prefix("#P") {
+            block = parse_coordinates(position, line_number)?;
+            row = 0;
+        } else if line.starts_with('#') {
+            continue;
+        } else {
+            for (x, character) in line.chars().enumerate() {
+                match character {
+                    '.' => {}
+                    '*' => {
+                        let cell = block
+                            .0
+                            .checked_add(x as i64)
+                            .zip(block.1.checked_add(row).and_then(i64::checked_neg));
+                        cells.push(cell.ok_or(PatternError::CellOutOfRange { line: line_number })?);
+                    }
+                    _ => {
+                        return Err(PatternError::UnexpectedCharacter {
+                            line: line_number,
+                            character,
+                        })
+                    }
+                }
+            }
+            row += 1;
+        }
+    }
+
+    let mut pattern = Pattern::from_cells(cells)?;
+    pattern.rule = rule;
+    pattern.comments = comments;
+    Ok(pattern)
+}
+
+/// Writes a pattern in the Life 1.05 format as a single `#P` block.
+pub(super) fn write_105(pattern: &Pattern) -> String {
+    let mut output = String::from("#Life 1.05\n");
+    for comment in &pattern.comments {
+        output.push_str(&format!("#D {comment}\n"));
+    }
+    match pattern.rule {
+        None => {}
+        Some(Rule::CONWAY) => output.push_str("#N\n"),
+        Some(rule) => output.push_str(&format!("#R {}\n", survival_birth(rule))),
+    }
+    output.push_str("#P 0 0\n");
+    for row in pattern.text_rows('*', '.') {
+        output.push_str(if row.is_empty() { "." } else { &row });
+        output.push('\n');
+    }
+    output
+}
+
+/// Parses a pattern in the Life 1.06 format, a list of `x y` coordinates where `y` counts downwards.
+pub(super) fn parse_106(input: &str) -> Result<Pattern, PatternError> {
+    let mut cells = Vec::new();
+
+    for (index, line) in input.lines().enumerate() {
+        let line_number = index + 1;
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let (x, y) = parse_coordinates(line, line_number)?;
+        let y = y
+            .checked_neg()
+            .ok_or(PatternError::CellOutOfRange { line: line_number })?;
+        cells.push((x, y));
+    }
+
+    Pattern::from_cells(cells)
+}
+
+/// Writes a pattern in the Life 1.06 format.
+pub(super) fn write_106(pattern: &Pattern) -> String {
+    let mut output = String::from("#Life 1.06\n");
+    for &(x, y) in &pattern.cells {
+        output.push_str(&format!("{x} {}\n", pattern.height - 1 - y));
+    }
+    output
+}
+
+/// Parses a pair of whitespace-separated integer coordinates.
+fn parse_coordinates(text: &str, line_number: usize) -> Result<(i64, i64), PatternError> {
+    let invalid_coordinates = || PatternError::InvalidCoordinates {
+        line: line_number,
+        text: text.trim().to_string(),
+    };
+
+    let mut parts = text.split_whitespace().map(str::parse::<i64>);
+    match (parts.next(), parts.next(), parts.next()) {
+        (Some(Ok(x)), Some(Ok(y)), None) => Ok((x, y)),
+        _ => Err(invalid_coordinates()),
+    }
+}
+
+/// Formats a rule in the `S/B` notation used by Life 1.05, e.g. `23/3`.
+fn survival_birth(rule: Rule) -> String {
+    let counts = |included: &dyn Fn(u8) -> bool| {
+        (0..=8)
+            .filter(|&n| included(n))
+            .map(|n| n.to_string())
+            .collect::<String>()
+    };
+    format!(
+        "{}/{}",
+        counts(&|n| rule.survives(n)),
+        counts(&|n| rule.is_born(n))
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rejects_life_105_cells_beyond_the_largest_coordinates() {
+        let input = "#Life 1.05\n#P 0 9223372036854775807\n*\n*\n";
+        assert_eq!(
+            parse_105(input),
+            Err(PatternError::CellOutOfRange { line: 4 })
+        );
+
+        let input = "#Life 1.05\n#P 9223372036854775807 0\n.*\n";
+        assert_eq!(
+            parse_105(input),
+            Err(PatternError::CellOutOfRange { line: 3 })
+        );
+    }
+
+    #[test]
+    fn rejects_life_106_cells_beyond_the_largest_coordinates() {
+        let input = "#Life 1.06\n0 0\n0 -9223372036854775808\n";
+        assert_eq!(
+            parse_106(input),
+            Err(PatternError::CellOutOfRange { line: 3 })
+        );
+    }
+
+    #[test]
+    fn rejects_life_106_cells_too_far_apart() {
+        let input = "#Life 1.06\n-9223372036854775808 0\n9223372036854775807 0\n";
+        assert_eq!(parse_106(input), Err(PatternError::TooLarge));
+    }
+}
@@ -0,0 +1,344 @@
+use std::{
+    fmt::{self, Display},
+    fs,
+    path::Path,
+    str::FromStr,
+};
+
+use crate::{Rule, RuleError};
+
+mod life;
+mod plaintext;
+mod rle;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum PatternError {
+    Io(String),
+    UnknownFormat(String),
+    InvalidHeader { line: usize, header: String },
+    InvalidRule { line: usize, error: RuleError },
+    UnexpectedCharacter { line: usize, character: char },
+    InvalidCoordinates { line: usize, text: String },
+    RunTooLong { line: usize, run: i64 },
+    CellOutOfRange { line: usize },
+    TooLarge,
+}
+
+impl std::error::Error for PatternError {}
+
+impl Display for PatternError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PatternError::Io(message) => write!(f, "Could not access pattern file: {message}"),
+            PatternError::UnknownFormat(format) => write!(
+                f,
+                "Unknown pattern format \"{format}\" (expected rle, cells, life105 or life106)."
+            ),
+            PatternError::InvalidHeader { line, header } => {
+                write!(f, "Line {line}: invalid header \"{header}\".")
+            }
+            PatternError::InvalidRule { line, error } => write!(f, "Line {line}: {error}"),
+            PatternError::UnexpectedCharacter { line, character } => {
+                write!(f, "Line {line}: unexpected character '{character}'.")
+            }
+            PatternError::InvalidCoordinates { line, text } => {
+                write!(f, "Line {line}: invalid coordinates \"{text}\".")
+            }
+            PatternError::RunTooLong { line, run } => {
+                write!(f, "Line {line}: run of {run} cells is too long.")
+            }
+            PatternError::CellOutOfRange { line } => {
+                write!(f, "Line {line}: cell is beyond the largest coordinates.")
+            }
+            PatternError::TooLarge => write!(f, "The pattern is too large."),
+        }
+    }
+}
+
+/// A file format which patterns can be read from and written to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PatternFormat {
+    /// The run-length encoded format used by Golly, LifeWiki and catagolue, usually with an `.rle` extension.
+    Rle,
+    /// The plaintext format, with `.` and `O` for each cell, usually with a `.cells` extension.
+    Plaintext,
+    /// The Life 1.05 format, with `.` and `*` for each cell in `#P` blocks, usually with a `.lif` extension.
+    Life105,
+    /// The Life 1.06 format, listing the coordinates of each live cell, usually with a `.lif` extension.
+    Life106,
+}
+
+impl PatternFormat {
+    /// Returns the format with the given file extension, if it is unambiguous.
+    pub fn from_extension(extension: &str) -> Option<Self> {
+        match extension.to_ascii_lowercase().as_str() {
+            "rle" => Some(PatternFormat::Rle),
+            "cells" => Some(PatternFormat::Plaintext),
+            _ => None,
+        }
+    }
+
+    /// Guesses the format of a pattern from its contents.
+    pub fn detect(input: &str) -> Self {
+        let first_line = input.lines().map(str::trim).find(|line| !line.is_empty());
+        match first_line {
+            Some(line) if line.starts_with("#Life 1.05") => PatternFormat::Life105,
+            Some(line) if line.starts_with("#Life 1.06") => PatternFormat::Life106,
+            Some(line) if line.starts_with('!') => PatternFormat::Plaintext,
+            Some(line) if line.chars().all(|c| matches!(c, '.' | 'O' | '*')) => {
+                PatternFormat::Plaintext
+            }
+            _ => PatternFormat::Rle,
+        }
+    }
+}
+
+impl FromStr for PatternFormat {
+    type Err = PatternError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "rle" => Ok(PatternFormat::Rle),
+            "cells" | "plaintext" => Ok(PatternFormat::Plaintext),
+            "life105" | "life1.05" => Ok(PatternFormat::Life105),
+            "life106" | "life1.06" => Ok(PatternFormat::Life106),
+            _ => Err(PatternError::UnknownFormat(s.to_string())),
+        }
+    }
+}
+
+impl Display for PatternFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            PatternFormat::Rle => "rle",
+            PatternFormat::Plaintext => "cells",
+            PatternFormat::Life105 => "life105",
+            PatternFormat::Life106 => "life106",
+        };
+        write!(f, "{name}")
+    }
+}
+
+/// A pattern of live cells, as stored in a pattern file.
+///
+/// Cells use the same orientation as the game, with `0,0` at the bottom-left of the pattern.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Pattern {
+    /// The live cells, sorted by `x` then `y`.
+    cells: Vec<(i64, i64)>,
+    width: i64,
+    height: i64,
+    rule: Option<Rule>,
+    name: Option<String>,
+    /// The text of each comment line, without any format-specific prefix.
+    comments: Vec<String>,
+}
+
+impl Pattern {
+    /// Creates a pattern from a list of live cells, moved so that their bounding box starts at `0,0`, or returns
+    /// [PatternError::TooLarge] if the cells are too far apart for its width or height to fit in an `i64`.
+    ///
+    /// # Example
+    /// ```
+    /// use game_of_life::{Pattern, PatternError};
+    ///
+    /// let pattern = Pattern::from_cells(vec![(-5, 10), (-3, 12)]).unwrap();
+    /// assert_eq!(pattern.cells(), &[(0, 0), (2, 2)]);
+    /// assert_eq!((pattern.width(), pattern.height()), (3, 3));
+    ///
+    /// let error = Pattern::from_cells(vec![(i64::MIN, 0), (i64::MAX, 0)]).unwrap_err();
+    /// assert_eq!(error, PatternError::TooLarge);
+    /// ```
+    pub fn from_cells(cells: Vec<(i64, i64)>) -> Result<Self, PatternError> {
+        let min_x = cells.iter().map(|&(x, _)| x).min().unwrap_or_default();
+        let max_x = cells.iter().map(|&(x, _)| x).max().unwrap_or_default();
+        let min_y = cells.iter().map(|&(_, y)| y).min().unwrap_or_default();
+        let max_y = cells.iter().map(|&(_, y)| y).max().unwrap_or_default();
+        let size = |min: i64, max: i64| max.checked_sub(min)?.checked_add(1);
+        if size(min_x, max_x).is_none() || size(min_y, max_y).is_none() {
+            return Err(PatternError::TooLarge);
+        }
+
+        let cells = cells
+            .into_iter()
+            .map(|(x, y)| (x - min_x, y - min_y))
+            .collect();
+
+        Ok(Self::with_size(cells, 0, 0))
+    }
+
+    /// Creates a pattern from a list of live cells with non-negative coordinates, at least `width` by `height` in size.
+    fn with_size(mut cells: Vec<(i64, i64)>, width: i64, height: i64) -> Self {
+        cells.sort_unstable();
+        cells.dedup();
+
+        let width = cells.iter().map(|&(x, _)| x + 1).fold(width, i64::max);
+        let height = cells.iter().map(|&(_, y)| y + 1).fold(height, i64::max);
+
+        Self {
+            cells,
+            width,
+            height,
+            rule: None,
+            name: None,
+            comments: Vec::new(),
+        }
+    }
+
+    /// Parses a pattern in the RLE format, including its `x = .., y = .., rule = ..` header and `#` comment lines.
+    ///
+    /// # Example
+    /// ```
+    /// use game_of_life::Pattern;
+    ///
+    /// let gosper_glider_gun = "\
+    /// #N Gosper glider gun
+    /// #C A true period 30 glider gun.
+    /// x = 36, y = 9, rule = B3/S23
+    /// 24bo$22bobo$12b2o6b2o12b2o$11bo3bo4b2o12b2o$2o8bo5bo3b2o$2o8bo3bob2o4bo
+    /// bo$10bo5bo7bo$11bo3bo$12b2o!
+    /// ";
+    /// let pattern = Pattern::from_rle(gosper_glider_gun).unwrap();
+    /// assert_eq!(pattern.cells().len(), 36);
+    /// assert_eq!(pattern.name(), Some("Gosper glider gun"));
+    /// assert_eq!(pattern.comments(), ["A true period 30 glider gun."]);
+    /// assert_eq!(Pattern::from_rle(&pattern.to_rle()).unwrap(), pattern);
+    ///
+    /// let glider = Pattern::from_rle("x = 3, y = 3\nbo$2bo$3o!").unwrap();
+    /// assert_eq!(glider.cells(), &[(0, 0), (1, 0), (1, 2), (2, 0), (2, 1)]);
+    /// assert_eq!(glider.to_rle(), "x = 3, y = 3, rule = B3/S23\nbo$2bo$3o!\n");
+    ///
+    /// let below_blank_row = Pattern::from_rle("x = 2, y = 2, rule = B3/S23\n$bo!").unwrap();
+    /// assert_eq!(below_blank_row.cells(), &[(1, 0)]);
+    /// assert_eq!(Pattern::from_rle(&below_blank_row.to_rle()).unwrap(), below_blank_row);
+    ///
+    /// let error = Pattern::from_rle("x = 1, y = 1\no999999999$o!").unwrap_err();
+    /// assert_eq!(error.to_string(), "Line 2: run of 999999999 cells is too long.");
+    /// ```
+    pub fn from_rle(input: &str) -> Result<Self, PatternError> {
+        rle::parse(input)
+    }
+
+    /// Writes the pattern in the RLE format.
+    pub fn to_rle(&self) -> String {
+        rle::write(self)
+    }
+
+    /// Parses a pattern in the given format.
+    ///
+    /// # Example
+    /// ```
+    /// use game_of_life::{Pattern, PatternFormat};
+    ///
+    /// let glider = Pattern::from_rle("x = 3, y = 3\nbo$2bo$3o!").unwrap();
+    ///
+    /// let plaintext = "!Name: Glider\n.O.\n..O\nOOO\n";
+    /// assert_eq!(PatternFormat::detect(plaintext), PatternFormat::Plaintext);
+    /// let pattern = Pattern::parse(plaintext, PatternFormat::Plaintext).unwrap();
+    /// assert_eq!(pattern.cells(), glider.cells());
+    /// assert_eq!(pattern.name(), Some("Glider"));
+    /// assert_eq!(pattern.write(PatternFormat::Plaintext), "!Name: Glider\n.O\n..O\nOOO\n");
+    ///
+    /// let life_105 = "#Life 1.05\n#D A glider\n#N\n#P -1 -1\n.*\n..*\n***\n";
+    /// let pattern = Pattern::parse(life_105, PatternFormat::detect(life_105)).unwrap();
+    /// assert_eq!(pattern.cells(), glider.cells());
+    /// assert_eq!(
+    ///     pattern.write(PatternFormat::Life105),
+    ///     "#Life 1.05\n#D A glider\n#N\n#P 0 0\n.*\n..*\n***\n",
+    /// );
+    ///
+    /// let life_106 = "#Life 1.06\n0 -1\n1 0\n-1 1\n0 1\n1 1\n";
+    /// let pattern = Pattern::parse(life_106, PatternFormat::detect(life_106)).unwrap();
+    /// assert_eq!(pattern.cells(), glider.cells());
+    /// assert_eq!(
+    ///     Pattern::parse(&pattern.write(PatternFormat::Life106), PatternFormat::Life106).unwrap(),
+    ///     pattern,
+    /// );
+    ///
+    /// let error = Pattern::parse("#Life 1.06\n0 0\n1 x\n", PatternFormat::Life106).unwrap_err();
+    /// assert_eq!(error.to_string(), "Line 3: invalid coordinates \"1 x\".");
+    /// ```
+    pub fn parse(input: &str, format: PatternFormat) -> Result<Self, PatternError> {
+        match format {
+            PatternFormat::Rle => rle::parse(input),
+            PatternFormat::Plaintext => plaintext::parse(input),
+            PatternFormat::Life105 => life::parse_105(input),
+            PatternFormat::Life106 => life::parse_106(input),
+        }
+    }
+
+    /// Writes the pattern in the given format.
+    pub fn write(&self, format: PatternFormat) -> String {
+        match format {
+            PatternFormat::Rle => rle::write(self),
+            PatternFormat::Plaintext => plaintext::write(self),
+            PatternFormat::Life105 => life::write_105(self),
+            PatternFormat::Life106 => life::write_106(self),
+        }
+    }
+
+    /// Reads a pattern from a file, choosing the format from its extension or, failing that, its contents.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, PatternError> {
+        let path = path.as_ref();
+        let input = fs::read_to_string(path).map_err(|err| PatternError::Io(err.to_string()))?;
+        let format = path
+            .extension()
+            .and_then(|extension| PatternFormat::from_extension(&extension.to_string_lossy()))
+            .unwrap_or_else(|| PatternFormat::detect(&input));
+        Self::parse(&input, format)
+    }
+
+    /// Writes the pattern to a file, choosing the format from its extension and defaulting to RLE.
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), PatternError> {
+        let path = path.as_ref();
+        let format = match path.extension() {
+            Some(extension) if extension.eq_ignore_ascii_case("lif") => PatternFormat::Life106,
+            Some(extension) => PatternFormat::from_extension(&extension.to_string_lossy())
+                .unwrap_or(PatternFormat::Rle),
+            None => PatternFormat::Rle,
+        };
+        fs::write(path, self.write(format)).map_err(|err| PatternError::Io(err.to_string()))
+    }
+
+    /// Returns each row of the pattern from top to bottom, drawn with the given characters and without trailing dead
+    /// cells.
+    fn text_rows(&self, alive: char, dead: char) -> Vec<String> {
+        let mut rows = vec![String::new(); self.height as usize];
+        for &(x, y) in &self.cells {
+            let row = &mut rows[(self.height - 1 - y) as usize];
+            while row.len() < x as usize {
+                row.push(dead);
+            }
+            row.push(alive);
+        }
+        rows
+    }
+
+    pub fn cells(&self) -> &[(i64, i64)] {
+        &self.cells
+    }
+
+    pub fn width(&self) -> i64 {
+        self.width
+    }
+
+    pub fn height(&self) -> i64 {
+        self.height
+    }
+
+    pub fn rule(&self) -> Option<Rule> {
+        self.rule
+    }
+
+    pub fn set_rule(&mut self, rule: Rule) {
+        self.rule = Some(rule);
+    }
+
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    pub fn comments(&self) -> &[String] {
+        &self.comments
+    }
+}
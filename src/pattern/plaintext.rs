@@ -0,0 +1,91 @@
+use super::{Pattern, PatternError};
+
+/// Parses a pattern in the plaintext format, where `!` lines are comments and each other line is a row of cells.
+pub(super) fn parse(input: &str) -> Result<Pattern, PatternError> {
+    let mut name = None;
+    let mut comments = Vec::new();
+    let mut rows = Vec::new();
+    // The number of blank lines at the end of the rows so far, which are not part of the pattern if nothing follows
+    let mut trailing_blank_lines = 0;
+
+    for (index, line) in input.lines().enumerate() {
+        let line_number = index + 1;
+
+        if let Some(comment) = line.strip_prefix('!') {
+            match comment.strip_prefix("Name:") {
+                Some(text) => name = Some(text.trim().to_string()),
+                None => comments.push(comment.trim().to_string()),
+            }
+            continue;
+        }
+
+        if line.trim().is_empty() {
+            trailing_blank_lines += 1;
+        } else {
+            trailing_blank_lines = 0;
+        }
+
+        let mut row = Vec::new();
+        for (x, character) in line.trim_end().chars().enumerate() {
+            match character {
+                '.' => {}
+                'O' | '*' => row.push(x as i64),
+                _ => {
+                    return Err(PatternError::UnexpectedCharacter {
+                        line: line_number,
+                        character,
+                    })
+                }
+            }
+        }
+        rows.push(row);
+    }
+
+    rows.truncate(rows.len() - trailing_blank_lines);
+    let height = rows.len() as i64;
+    let cells = rows
+        .iter()
+        .enumerate()
+        .flat_map(|(row, xs)| xs.iter().map(move |&x| (x, height - 1 - row as i64)))
+        .collect();
+
+    let mut pattern = Pattern::with_size(cells, 0, height);
+    pattern.name = name;
+    pattern.comments = comments;
+    Ok(pattern)
+}
+
+/// Writes a pattern in the plaintext format, including its name and comments.
+pub(super) fn write(pattern: &Pattern) -> String {
+    let mut output = String::new();
+    if let Some(name) = &pattern.name {
+        output.push_str(&format!("!Name: {name}\n"));
+    }
+    for comment in &pattern.comments {
+        output.push_str(&format!("!{comment}\n"));
+    }
+    for row in pattern.text_rows('O', '.') {
+        output.push_str(if row.is_empty() { "." } else { &row });
+        output.push('\n');
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ignores_trailing_blank_lines() {
+        let pattern = parse("OOO\n\n  \n").unwrap();
+        assert_eq!(pattern.height, 1);
+        assert_eq!(pattern.cells, vec![(0, 0), (1, 0), (2, 0)]);
+    }
+
+    #[test]
+    fn keeps_blank_lines_between_rows() {
+        let pattern = parse("O\n\nO\n").unwrap();
+        assert_eq!(pattern.height, 3);
+        assert_eq!(pattern.cells, vec![(0, 0), (0, 2)]);
+    }
+}
@@ -0,0 +1,312 @@
+use std::collections::BTreeMap;
+
+use super::{Pattern, PatternError};
+use crate::Rule;
+
+/// The maximum length of a line of run-length encoded cells.
+const MAX_LINE_LENGTH: usize = 70;
+
+/// The longest run accepted, which is far wider than any practical pattern.
+const MAX_RUN: i64 = 1 << 20;
+
+/// The most live cells accepted in a pattern.
+const MAX_CELLS: usize = 1 << 22;
+
+/// The largest area accepted for a pattern, which keeps writing it out as text rows within a few hundred megabytes.
+const MAX_AREA: i64 = 1 << 28;
+
+/// Parses a pattern in the run-length encoded format used by Golly and LifeWiki.
+pub(super) fn parse(input: &str) -> Result<Pattern, PatternError> {
+    let mut name = None;
+    let mut comments = Vec::new();
+    let mut rule = None;
+    // The size given by the header, and the line it is on
+    let mut size = None;
+    // Cells as `(x, row)`, where rows count down from the top
+    let mut cells: Vec<(i64, i64)> = Vec::new();
+    let mut row = 0;
+    let mut x = 0;
+    let mut width = 0;
+    let mut finished = false;
+
+    for (index, line) in input.lines().enumerate() {
+        let line_number = index + 1;
+        let line = line.trim();
+
+        if finished || line.is_empty() {
+            continue;
+        } else if let Some(comment) = line.strip_prefix('#') {
+            let mut characters = comment.chars();
+            let tag = characters.next();
+            let text = characters.as_str().trim();
+            match tag {
+                Some('N') => name = Some(text.to_string()),
+                Some('C' | 'c') => comments.push(text.to_string()),
+                Some('r') => {
+                    rule = Some(text.parse().map_err(|error| PatternError::InvalidRule {
+                        line: line_number,
+                        error,
+                    })?)
+                }
+                _ => comments.push(comment.trim().to_string()),
+            }
+            continue;
+        } else if size.is_none() && line.starts_with('x') {
+            let (header_size, header_rule) = parse_header(line, line_number)?;
+            size = Some((header_size, line_number));
+            rule = header_rule.or(rule);
+            continue;
+        }
+
+        let mut count = String::new();
+        for character in line.chars() {
+            if character.is_ascii_digit() {
+                count.push(character);
+                continue;
+            } else if character.is_whitespace() {
+                continue;
+            }
+
+            let run: i64 = if count.is_empty() {
+                1
+            } else {
+                count
+                    .parse()
+                    .map_err(|_| PatternError::UnexpectedCharacter {
+                        line: line_number,
+                        character,
+                    })?
+            };
+            count.clear();
+            if run > MAX_RUN {
+                return Err(PatternError::RunTooLong {
+                    line: line_number,
+                    run,
+                });
+            }
+
+            match character {
+                'b' | '.' => x += run,
+                'o' => {
+                    if cells.len() + run as usize > MAX_CELLS {
+                        return Err(PatternError::TooLarge);
+                    }
+                    cells.extend((x..x + run).map(|x| (x, row)));
+                    x += run;
+                }
+                '$' => {
+                    row += run;
+                    x = 0;
+                }
+                '!' => {
+                    finished = true;
+                    break;
+                }
+                _ => {
+                    return Err(PatternError::UnexpectedCharacter {
+                        line: line_number,
+                        character,
+                    })
+                }
+            }
+            width = width.max(x);
+            // Checked after every run, so the position cannot overflow before it is caught
+            if width > MAX_AREA || row >= MAX_AREA {
+                return Err(PatternError::TooLarge);
+            }
+        }
+    }
+
+    let height = row + 1;
+    if width * height > MAX_AREA {
+        return Err(PatternError::TooLarge);
+    }
+    // The header may not claim more space than the cells take up, since it is not otherwise limited
+    if let Some(((header_width, header_height), line_number)) = size {
+        if header_width > width || header_height > height {
+            return Err(PatternError::InvalidHeader {
+                line: line_number,
+                header: format!("x = {header_width}, y = {header_height}"),
+            });
+        }
+    }
+    let cells = cells
+        .into_iter()
+        .map(|(x, row)| (x, height - 1 - row))
+        .collect();
+
+    let mut pattern = Pattern::with_size(cells, width, height);
+    pattern.rule = rule;
+    pattern.name = name;
+    pattern.comments = comments;
+    Ok(pattern)
+}
+
+/// Parses a header line such as `x = 3, y = 3, rule = B3/S23`, returning the size and rule.
+fn parse_header(
+    line: &str,
+    line_number: usize,
+) -> Result<((i64, i64), Option<Rule>), PatternError> {
+    let invalid_header = || PatternError::InvalidHeader {
+        line: line_number,
+        header: line.to_string(),
+    };
+
+    let mut width = None;
+    let mut height = None;
+    let mut rule = None;
+
+    for entry in line.split(',') {
+        let (key, value) = entry.split_once('=').ok_or_else(invalid_header)?;
+        let value = value.trim();
+        match key.trim() {
+            "x" => width = Some(value.parse().map_err(|_| invalid_header())?),
+            "y" => height = Some(value.parse().map_err(|_| invalid_header())?),
+            "rule" => {
+                // Golly appends bounded grid specifications such as `:T20,20` to the rule
+                let value = value.split(':').next().unwrap_or_default();
+                rule = Some(value.parse().map_err(|error| PatternError::InvalidRule {
+                    line: line_number,
+                    error,
+                })?);
+            }
+            _ => return Err(invalid_header()),
+        }
+    }
+
+    match (width, height) {
+        (Some(width), Some(height)) if width >= 0 && height >= 0 => Ok(((width, height), rule)),
+        _ => Err(invalid_header()),
+    }
+}
+
+/// Writes a pattern in the run-length encoded format, including its comments and rule.
+pub(super) fn write(pattern: &Pattern) -> String {
+    let mut output = String::new();
+    if let Some(name) = &pattern.name {
+        output.push_str(&format!("#N {name}\n"));
+    }
+    for comment in &pattern.comments {
+        output.push_str(&format!("#C {comment}\n"));
+    }
+    output.push_str(&format!(
+        "x = {}, y = {}, rule = {}\n",
+        pattern.width,
+        pattern.height,
+        pattern.rule.unwrap_or_default()
+    ));
+
+    let mut rows: BTreeMap<i64, Vec<i64>> = BTreeMap::new();
+    for &(x, y) in &pattern.cells {
+        rows.entry(y).or_default().push(x);
+    }
+
+    let mut tokens = Vec::new();
+    // Blank rows at the top are written too, so that cells keep their distance from the top of the pattern
+    let mut previous_y = pattern.height - 1;
+    let mut x = 0;
+    let mut widest = 0;
+    for (&y, row) in rows.iter_mut().rev() {
+        row.sort_unstable();
+        if previous_y > y {
+            tokens.push(run_token(previous_y - y, '$'));
+        }
+        previous_y = y;
+
+        x = 0;
+        let mut cells = row.iter().peekable();
+        while let Some(&start) = cells.next() {
+            if start > x {
+                tokens.push(run_token(start - x, 'b'));
+            }
+            let mut end = start + 1;
+            while cells.next_if(|&&next| next == end).is_some() {
+                end += 1;
+            }
+            tokens.push(run_token(end - start, 'o'));
+            x = end;
+        }
+        widest = widest.max(x);
+    }
+    // As are blank rows at the bottom and blank columns on the right, since a header may not be larger than the cells
+    if previous_y > 0 {
+        tokens.push(run_token(previous_y, '$'));
+        x = 0;
+    }
+    if widest < pattern.width {
+        tokens.push(run_token(pattern.width - x, 'b'));
+    }
+    tokens.push("!".to_string());
+
+    let mut line = String::new();
+    for token in tokens {
+        if line.len() + token.len() > MAX_LINE_LENGTH {
+            output.push_str(&line);
+            output.push('\n');
+            line.clear();
+        }
+        line.push_str(&token);
+    }
+    output.push_str(&line);
+    output.push('\n');
+    output
+}
+
+fn run_token(run: i64, tag: char) -> String {
+    if run == 1 {
+        tag.to_string()
+    } else {
+        format!("{run}{tag}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rejects_negative_header_sizes() {
+        assert!(matches!(
+            parse("x = -1, y = 3\nbo$2bo$3o!"),
+            Err(PatternError::InvalidHeader { line: 1, .. })
+        ));
+    }
+
+    #[test]
+    fn rejects_header_sizes_larger_than_the_cells() {
+        assert!(matches!(
+            parse("#C A glider\nx = 50000000, y = 50000000\nbo$2bo$3o!"),
+            Err(PatternError::InvalidHeader { line: 2, .. })
+        ));
+        assert!(matches!(
+            parse("x = 3, y = 4\nbo$2bo$3o!"),
+            Err(PatternError::InvalidHeader { line: 1, .. })
+        ));
+    }
+
+    #[test]
+    fn writes_blank_rows_and_columns_covered_by_the_size() {
+        let pattern = parse("x = 4, y = 3, rule = B3/S23\n$o3b$!").unwrap();
+        assert_eq!((pattern.width, pattern.height), (4, 3));
+        assert_eq!(write(&pattern), "x = 4, y = 3, rule = B3/S23\n$o$4b!\n");
+        assert_eq!(parse(&write(&pattern)).unwrap(), pattern);
+    }
+
+    #[test]
+    fn rejects_patterns_with_too_many_cells() {
+        let rows = "1048576o$".repeat(5);
+        assert_eq!(parse(&format!("{rows}!")), Err(PatternError::TooLarge));
+    }
+
+    #[test]
+    fn rejects_patterns_covering_too_large_an_area() {
+        let row = "1048576bo".repeat(300);
+        assert_eq!(parse(&format!("{row}!")), Err(PatternError::TooLarge));
+
+        let rows = "1048576$".repeat(20);
+        assert_eq!(
+            parse(&format!("1048576o{rows}o!")),
+            Err(PatternError::TooLarge)
+        );
+    }
+}
@@ -0,0 +1,198 @@
+use std::{
+    collections::{hash_map::DefaultHasher, HashMap, VecDeque},
+    fmt::{self, Display},
+    hash::{Hash, Hasher},
+};
+
+/// The number of generations remembered by default, which is the longest period which can be detected.
+const DEFAULT_MAX_HISTORY: usize = 1024;
+
+/// How a pattern behaves once it has settled into a repeating cycle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Stability {
+    /// Every cell has died.
+    Extinct,
+    /// The pattern no longer changes.
+    StillLife,
+    /// The pattern returns to the same state in the same place every `period` generations.
+    Oscillator { period: u64 },
+    /// The pattern returns to the same state every `period` generations, moved by `dx` and `dy` cells.
+    Spaceship { period: u64, dx: i64, dy: i64 },
+}
+
+impl Display for Stability {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Stability::Extinct => write!(f, "extinct"),
+            Stability::StillLife => write!(f, "still life"),
+            Stability::Oscillator { period } => write!(f, "oscillator with period {period}"),
+            Stability::Spaceship { period, dx, dy } => write!(
+                f,
+                "spaceship with period {period}, moving {dx},{dy} each period"
+            ),
+        }
+    }
+}
+
+/// Detects when a pattern repeats an earlier state by remembering the shape of each generation, looked up by its hash.
+///
+/// States are compared by shape, ignoring where the pattern is, so that spaceships are detected as well as still
+/// lifes and oscillators. Shapes with the same hash are compared cell by cell, so a collision is never mistaken for a
+/// repeat.
+///
+/// # Example
+/// ```
+/// use game_of_life::{PeriodDetector, Rule, SparseGrid, Stability, Universe};
+///
+/// let glider = vec![(1, 2), (2, 1), (0, 0), (1, 0), (2, 0)];
+/// let blinker = vec![(10, 0), (11, 0), (12, 0)];
+///
+/// for (cells, expected) in [
+///     (glider, Stability::Spaceship { period: 4, dx: 1, dy: -1 }),
+///     (blinker, Stability::Oscillator { period: 2 }),
+/// ] {
+///     let mut grid = SparseGrid::new(cells, Rule::CONWAY);
+///     let mut detector = PeriodDetector::new();
+///     let mut generation = 0;
+///     let stability = loop {
+///         if let Some(stability) = detector.observe(generation, &grid.live_cells()) {
+///             break stability;
+///         }
+///         grid.step_forward();
+///         generation += 1;
+///     };
+///     assert_eq!(stability, expected);
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct PeriodDetector {
+    /// The latest state with each hash.
+    states: HashMap<u64, State>,
+    /// The hashes of the remembered generations, oldest first.
+    history: VecDeque<u64>,
+    max_history: usize,
+}
+
+/// A generation remembered by a [PeriodDetector].
+#[derive(Debug, Clone)]
+struct State {
+    generation: u64,
+    /// The bottom-left corner of the pattern.
+    corner: (i64, i64),
+    /// The live cells relative to the corner, sorted.
+    shape: Box<[(i64, i64)]>,
+}
+
+impl Default for PeriodDetector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PeriodDetector {
+    pub fn new() -> Self {
+        Self {
+            states: HashMap::new(),
+            history: VecDeque::new(),
+            max_history: DEFAULT_MAX_HISTORY,
+        }
+    }
+
+    /// Sets the number of generations remembered, which is the longest period which can be detected.
+    pub fn with_max_history(mut self, max_history: usize) -> Self {
+        self.max_history = max_history.max(1);
+        self
+    }
+
+    /// Forgets every generation observed so far.
+    pub fn clear(&mut self) {
+        self.states.clear();
+        self.history.clear();
+    }
+
+    /// Remembers the live cells of a generation, returning how the pattern behaves if it repeats a remembered state.
+    ///
+    /// Generations should be observed in order without gaps, or the periods found may be multiples of the true ones.
+    pub fn observe(&mut self, generation: u64, cells: &[(i64, i64)]) -> Option<Stability> {
+        if cells.is_empty() {
+            return Some(Stability::Extinct);
+        }
+
+        let min_x = cells.iter().map(|&(x, _)| x).min().unwrap_or_default();
+        let min_y = cells.iter().map(|&(_, y)| y).min().unwrap_or_default();
+        let mut shape: Vec<(i64, i64)> =
+            cells.iter().map(|&(x, y)| (x - min_x, y - min_y)).collect();
+        shape.sort_unstable();
+
+        let hash = hash_shape(&shape);
+
+        let state = State {
+            generation,
+            corner: (min_x, min_y),
+            shape: shape.into_boxed_slice(),
+        };
+        // A different shape with the same hash replaces the earlier one without being counted as a repeat
+        let previous = self
+            .states
+            .insert(hash, state)
+            .filter(|previous| previous.shape == self.states[&hash].shape);
+        self.history.push_back(hash);
+        if self.history.len() > self.max_history {
+            let oldest = self.history.pop_front().unwrap();
+            // Only forget the state if it has not been seen again since
+            if self
+                .states
+                .get(&oldest)
+                .is_some_and(|state| state.generation + self.max_history as u64 <= generation)
+            {
+                self.states.remove(&oldest);
+            }
+        }
+
+        let State {
+            generation: earlier,
+            corner: (earlier_x, earlier_y),
+            ..
+        } = previous?;
+        let period = generation
+            .checked_sub(earlier)
+            .filter(|&period| period > 0)?;
+        let (dx, dy) = (min_x - earlier_x, min_y - earlier_y);
+
+        Some(match (period, dx, dy) {
+            (1, 0, 0) => Stability::StillLife,
+            (_, 0, 0) => Stability::Oscillator { period },
+            _ => Stability::Spaceship { period, dx, dy },
+        })
+    }
+}
+
+fn hash_shape(shape: &[(i64, i64)]) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    shape.hash(&mut hasher);
+    hasher.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shapes_with_the_same_hash_are_not_repeats() {
+        let blinker = [(0, 0), (1, 0), (2, 0)];
+        let mut detector = PeriodDetector::new();
+        // Remember a different shape under the blinker's hash, as if the two collided
+        detector.states.insert(
+            hash_shape(&blinker),
+            State {
+                generation: 0,
+                corner: (0, 0),
+                shape: Box::new([(0, 0)]),
+            },
+        );
+        detector.history.push_back(hash_shape(&blinker));
+
+        assert_eq!(detector.observe(1, &blinker), None);
+        assert_eq!(detector.observe(2, &blinker), Some(Stability::StillLife));
+    }
+}
@@ -0,0 +1,67 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::Region;
+
+/// A small, fast pseudo-random number generator (SplitMix64).
+///
+/// The sequence depends only on the seed, so a soup generated from a given seed is identical on every platform and
+/// version of the crate.
+#[derive(Debug, Clone)]
+pub struct Random {
+    state: u64,
+}
+
+impl Random {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    /// Returns a seed derived from the current time.
+    pub fn seed_from_time() -> u64 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |duration| duration.as_nanos() as u64)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// Returns a number in the range `0.0..1.0`.
+    pub fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+/// Returns the live cells of a random soup filling `region`, where each cell is alive with probability `density`.
+///
+/// # Example
+/// ```
+/// use game_of_life::{random_soup, Region};
+///
+/// let region = Region { x: 0, y: 0, width: 20, height: 10 };
+/// let soup = random_soup(42, 0.5, region);
+/// assert_eq!(soup, random_soup(42, 0.5, region));
+/// assert_ne!(soup, random_soup(43, 0.5, region));
+/// assert!(soup.iter().all(|&(x, y)| (0..20).contains(&x) && (0..10).contains(&y)));
+/// assert!(random_soup(42, 0.0, region).is_empty());
+/// assert_eq!(random_soup(42, 1.0, region).len(), 200);
+/// ```
+pub fn random_soup(seed: u64, density: f64, region: Region) -> Vec<(i64, i64)> {
+    let mut random = Random::new(seed);
+    let mut cells = Vec::new();
+
+    for y in region.y..region.y + region.height {
+        for x in region.x..region.x + region.width {
+            if random.next_f64() < density {
+                cells.push((x, y));
+            }
+        }
+    }
+
+    cells
+}
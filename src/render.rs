@@ -0,0 +1,230 @@
+use std::io::{self, BufWriter, Stdout, Write};
+
+use crossterm::terminal;
+
+use crate::{Region, Universe};
+
+const ENTER_ALTERNATE_SCREEN: &str = "\x1b[?1049h";
+const LEAVE_ALTERNATE_SCREEN: &str = "\x1b[?1049l";
+const HIDE_CURSOR: &str = "\x1b[?25l";
+const SHOW_CURSOR: &str = "\x1b[?25h";
+const CLEAR_SCREEN: &str = "\x1b[2J";
+const CLEAR_TO_END_OF_LINE: &str = "\x1b[K";
+
+/// Returns the rows of a region of a universe from top to bottom, with `O` for live cells and `.` for dead cells.
+///
+/// # Example
+/// ```
+/// use game_of_life::{frame_lines, Region, SparseGrid, Rule};
+///
+/// let grid = SparseGrid::new(vec![(0, 0), (1, 1)], Rule::CONWAY);
+/// let region = Region { x: 0, y: 0, width: 3, height: 2 };
+/// assert_eq!(frame_lines(&grid, region), [". O .", "O . ."]);
+/// ```
+pub fn frame_lines<U: Universe + ?Sized>(universe: &U, region: Region) -> Vec<String> {
+    (region.y..region.y + region.height)
+        .rev()
+        .map(|y| {
+            (region.x..region.x + region.width)
+                .map(|x| if universe.is_alive(x, y) { "O" } else { "." })
+                .collect::<Vec<_>>()
+                .join(" ")
+        })
+        .collect()
+}
+
+/// A front end which frames of a [Game](crate::Game) are drawn to, such as a terminal or a window.
+///
+/// # Example
+/// A renderer which keeps every frame instead of drawing it:
+/// ```
+/// use std::{cell::RefCell, io, rc::Rc};
+/// use game_of_life::{Config, Game, Renderer};
+///
+/// struct Frames(Rc<RefCell<Vec<Vec<String>>>>);
+///
+/// impl Renderer for Frames {
+///     fn draw(&mut self, lines: &[String]) -> io::Result<()> {
+///         self.0.borrow_mut().push(lines.to_vec());
+///         Ok(())
+///     }
+/// }
+///
+/// let frames = Rc::new(RefCell::new(Vec::new()));
+/// let args = vec!["3", "3", "0", "0,1", "1,1", "2,1"];
+/// let mut game = Game::new(Config::build(args.into_iter().map(String::from).collect()).unwrap())
+///     .with_renderer(Frames(Rc::clone(&frames)));
+/// game.step().unwrap();
+///
+/// assert_eq!(*frames.borrow(), [[". O .", ". O .", ". O ."]]);
+/// ```
+pub trait Renderer {
+    /// Draws a frame, given as lines of text from top to bottom.
+    fn draw(&mut self, lines: &[String]) -> io::Result<()>;
+
+    /// Returns the number of columns and rows of text which fit on the screen, if there is a limit.
+    fn size(&self) -> Option<(u16, u16)> {
+        None
+    }
+}
+
+/// Draws frames in-place in a terminal using ANSI escape sequences.
+///
+/// The first frame switches to the terminal's alternate screen, and each later frame only rewrites the lines which
+/// changed. The terminal is restored when the renderer is dropped, leaving the last frame visible.
+pub struct AnsiRenderer<W: Write> {
+    output: BufWriter<W>,
+    previous_frame: Vec<String>,
+    /// Whether the alternate screen is currently in use.
+    active: bool,
+}
+
+impl AnsiRenderer<Stdout> {
+    /// Creates a renderer which draws to standard output.
+    pub fn stdout() -> Self {
+        Self::new(io::stdout())
+    }
+}
+
+impl<W: Write> AnsiRenderer<W> {
+    pub fn new(output: W) -> Self {
+        Self {
+            output: BufWriter::new(output),
+            previous_frame: Vec::new(),
+            active: false,
+        }
+    }
+
+    /// Draws a frame, rewriting only the lines which differ from the previous frame.
+    pub fn draw(&mut self, lines: &[String]) -> io::Result<()> {
+        if !self.active {
+            write!(
+                self.output,
+                "{ENTER_ALTERNATE_SCREEN}{HIDE_CURSOR}{CLEAR_SCREEN}"
+            )?;
+            self.active = true;
+            self.previous_frame.clear();
+        }
+
+        let line_count = lines.len().max(self.previous_frame.len());
+        for row in 0..line_count {
+            let line = lines.get(row).map_or("", String::as_str);
+            if self.previous_frame.get(row).map(String::as_str) == Some(line) {
+                continue;
+            }
+            write!(
+                self.output,
+                "\x1b[{};1H{line}{CLEAR_TO_END_OF_LINE}",
+                row + 1
+            )?;
+        }
+
+        self.previous_frame = lines.to_vec();
+        self.output.flush()
+    }
+
+    /// Leaves the alternate screen and shows the cursor again, then reprints the last frame so it stays visible.
+    pub fn restore(&mut self) -> io::Result<()> {
+        if !self.active {
+            return Ok(());
+        }
+        self.active = false;
+
+        write!(self.output, "{SHOW_CURSOR}{LEAVE_ALTERNATE_SCREEN}")?;
+        for line in &self.previous_frame {
+            writeln!(self.output, "{line}")?;
+        }
+        self.output.flush()
+    }
+}
+
+impl<W: Write> Renderer for AnsiRenderer<W> {
+    fn draw(&mut self, lines: &[String]) -> io::Result<()> {
+        AnsiRenderer::draw(self, lines)
+    }
+
+    /// Returns the size of the terminal, if there is one.
+    fn size(&self) -> Option<(u16, u16)> {
+        terminal::size().ok()
+    }
+}
+
+impl<W: Write> Drop for AnsiRenderer<W> {
+    fn drop(&mut self) {
+        let _ = self.restore();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lines(lines: &[&str]) -> Vec<String> {
+        lines.iter().map(|&line| String::from(line)).collect()
+    }
+
+    /// Returns what has been written since the last call, and forgets it.
+    fn take_output(renderer: &mut AnsiRenderer<Vec<u8>>) -> String {
+        String::from_utf8(std::mem::take(renderer.output.get_mut())).unwrap()
+    }
+
+    #[test]
+    fn rewrites_only_changed_lines() {
+        let mut renderer = AnsiRenderer::new(Vec::new());
+        renderer.draw(&lines(&["O . .", ". O .", ". . O"])).unwrap();
+        assert_eq!(
+            take_output(&mut renderer),
+            format!(
+                "{ENTER_ALTERNATE_SCREEN}{HIDE_CURSOR}{CLEAR_SCREEN}\x1b[1;1HO . .{CLEAR_TO_END_OF_LINE}\
+                 \x1b[2;1H. O .{CLEAR_TO_END_OF_LINE}\x1b[3;1H. . O{CLEAR_TO_END_OF_LINE}"
+            )
+        );
+
+        renderer.draw(&lines(&["O . .", "O O O", ". . O"])).unwrap();
+        assert_eq!(
+            take_output(&mut renderer),
+            format!("\x1b[2;1HO O O{CLEAR_TO_END_OF_LINE}")
+        );
+
+        renderer.draw(&lines(&["O . .", "O O O", ". . O"])).unwrap();
+        assert_eq!(take_output(&mut renderer), "");
+    }
+
+    #[test]
+    fn clears_lines_no_longer_drawn() {
+        let mut renderer = AnsiRenderer::new(Vec::new());
+        renderer.draw(&lines(&["a", "b", "c"])).unwrap();
+        take_output(&mut renderer);
+
+        renderer.draw(&lines(&["a"])).unwrap();
+        assert_eq!(
+            take_output(&mut renderer),
+            format!("\x1b[2;1H{CLEAR_TO_END_OF_LINE}\x1b[3;1H{CLEAR_TO_END_OF_LINE}")
+        );
+    }
+
+    #[test]
+    fn restore_shows_the_cursor_and_last_frame_once() {
+        let mut renderer = AnsiRenderer::new(Vec::new());
+        renderer.restore().unwrap();
+        assert_eq!(take_output(&mut renderer), "");
+
+        renderer.draw(&lines(&["a", "b"])).unwrap();
+        take_output(&mut renderer);
+        renderer.restore().unwrap();
+        assert_eq!(
+            take_output(&mut renderer),
+            format!("{SHOW_CURSOR}{LEAVE_ALTERNATE_SCREEN}a\nb\n")
+        );
+
+        renderer.restore().unwrap();
+        assert_eq!(take_output(&mut renderer), "");
+
+        // Drawing again re-enters the alternate screen and redraws every line
+        renderer.draw(&lines(&["a"])).unwrap();
+        assert_eq!(
+            take_output(&mut renderer),
+            format!("{ENTER_ALTERNATE_SCREEN}{HIDE_CURSOR}{CLEAR_SCREEN}\x1b[1;1Ha{CLEAR_TO_END_OF_LINE}")
+        );
+    }
+}
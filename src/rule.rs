@@ -0,0 +1,212 @@
+use std::{
+    fmt::{self, Display},
+    str::FromStr,
+};
+
+use crate::State;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum RuleError {
+    MissingSeparator,
+    InvalidNeighbourCount(char),
+    InvalidPrefix(String),
+}
+
+impl std::error::Error for RuleError {}
+
+impl Display for RuleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RuleError::MissingSeparator => {
+                write!(f, "Rulestring must contain a single '/' separator.")
+            }
+            RuleError::InvalidNeighbourCount(c) => {
+                write!(f, "'{c}' is not a valid neighbour count (expected 0-8).")
+            }
+            RuleError::InvalidPrefix(part) => {
+                write!(
+                    f,
+                    "Expected one 'B' part and one 'S' part, found \"{part}\"."
+                )
+            }
+        }
+    }
+}
+
+/// An outer-totalistic rule, describing how many live neighbours cause a dead cell to be born and a live cell to survive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rule {
+    /// Bit `n` is set if a dead cell with `n` live neighbours becomes alive.
+    birth: u16,
+    /// Bit `n` is set if a live cell with `n` live neighbours stays alive.
+    survival: u16,
+}
+
+impl Rule {
+    /// Conway's Game of Life, `B3/S23`.
+    pub const CONWAY: Rule = Rule {
+        birth: 1 << 3,
+        survival: (1 << 2) | (1 << 3),
+    };
+
+    /// Creates a rule from the neighbour counts which cause birth and survival.
+    ///
+    /// Counts above `8` are ignored.
+    pub fn new(birth: &[u8], survival: &[u8]) -> Self {
+        Self {
+            birth: to_mask(birth),
+            survival: to_mask(survival),
+        }
+    }
+
+    /// Returns `true` if a dead cell with `live_neighbours` live neighbours becomes alive.
+    pub fn is_born(&self, live_neighbours: u8) -> bool {
+        live_neighbours <= 8 && self.birth & (1 << live_neighbours) != 0
+    }
+
+    /// Returns `true` if a live cell with `live_neighbours` live neighbours stays alive.
+    pub fn survives(&self, live_neighbours: u8) -> bool {
+        live_neighbours <= 8 && self.survival & (1 << live_neighbours) != 0
+    }
+
+    /// Returns the state of a cell in the next generation.
+    pub fn next_state(&self, current_state: State, live_neighbours: u8) -> State {
+        let alive = match current_state {
+            State::Alive => self.survives(live_neighbours),
+            State::Dead => self.is_born(live_neighbours),
+        };
+
+        if alive {
+            State::Alive
+        } else {
+            State::Dead
+        }
+    }
+}
+
+impl Default for Rule {
+    fn default() -> Self {
+        Rule::CONWAY
+    }
+}
+
+impl FromStr for Rule {
+    type Err = RuleError;
+
+    /// Parses a rulestring in either `B36/S23` or `23/36` (survival/birth) notation.
+    ///
+    /// # Example
+    /// ```
+    /// use game_of_life::Rule;
+    ///
+    /// let highlife: Rule = "B36/S23".parse().unwrap();
+    /// assert_eq!(highlife, "23/36".parse().unwrap());
+    /// assert!(highlife.is_born(6));
+    /// assert!(!highlife.survives(6));
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (first, second) = s
+            .trim()
+            .split_once('/')
+            .ok_or(RuleError::MissingSeparator)?;
+        if second.contains('/') {
+            return Err(RuleError::MissingSeparator);
+        }
+
+        let first_prefix = first.chars().next().map(|c| c.to_ascii_uppercase());
+        let second_prefix = second.chars().next().map(|c| c.to_ascii_uppercase());
+
+        let (birth, survival) = match (first_prefix, second_prefix) {
+            (Some('B'), Some('S')) => (&first[1..], &second[1..]),
+            (Some('S'), Some('B')) => (&second[1..], &first[1..]),
+            (Some('B' | 'S'), _) => return Err(RuleError::InvalidPrefix(second.to_string())),
+            (_, Some('B' | 'S')) => return Err(RuleError::InvalidPrefix(first.to_string())),
+            _ => (second, first),
+        };
+
+        Ok(Self {
+            birth: parse_counts(birth)?,
+            survival: parse_counts(survival)?,
+        })
+    }
+}
+
+impl Display for Rule {
+    /// Formats the rule in `B3/S23` notation.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "B")?;
+        for n in (0..=8).filter(|&n| self.is_born(n)) {
+            write!(f, "{n}")?;
+        }
+        write!(f, "/S")?;
+        for n in (0..=8).filter(|&n| self.survives(n)) {
+            write!(f, "{n}")?;
+        }
+        Ok(())
+    }
+}
+
+fn to_mask(counts: &[u8]) -> u16 {
+    counts
+        .iter()
+        .filter(|&&n| n <= 8)
+        .fold(0, |mask, &n| mask | (1 << n))
+}
+
+fn parse_counts(digits: &str) -> Result<u16, RuleError> {
+    let mut mask = 0;
+    for c in digits.chars() {
+        match c.to_digit(10) {
+            Some(n) if n <= 8 => mask |= 1 << n,
+            _ => return Err(RuleError::InvalidNeighbourCount(c)),
+        }
+    }
+    Ok(mask)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(rulestring: &str) -> Result<Rule, RuleError> {
+        rulestring.parse()
+    }
+
+    #[test]
+    fn rejects_missing_separator() {
+        assert_eq!(parse(""), Err(RuleError::MissingSeparator));
+        assert_eq!(parse("B3S23"), Err(RuleError::MissingSeparator));
+        assert_eq!(parse("B3/S23/S4"), Err(RuleError::MissingSeparator));
+    }
+
+    #[test]
+    fn rejects_missing_prefix() {
+        assert_eq!(
+            parse("B3/23"),
+            Err(RuleError::InvalidPrefix(String::from("23")))
+        );
+        assert_eq!(
+            parse("3/S23"),
+            Err(RuleError::InvalidPrefix(String::from("3")))
+        );
+    }
+
+    #[test]
+    fn rejects_duplicate_sections() {
+        assert_eq!(
+            parse("B3/B23"),
+            Err(RuleError::InvalidPrefix(String::from("B23")))
+        );
+        assert_eq!(
+            parse("S23/S3"),
+            Err(RuleError::InvalidPrefix(String::from("S3")))
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_neighbour_counts() {
+        assert_eq!(parse("B3a/S23"), Err(RuleError::InvalidNeighbourCount('a')));
+        assert_eq!(parse("B39/S23"), Err(RuleError::InvalidNeighbourCount('9')));
+        assert_eq!(parse("23/3-"), Err(RuleError::InvalidNeighbourCount('-')));
+    }
+}
@@ -0,0 +1,216 @@
+use std::{
+    fmt::{self, Display},
+    str::FromStr,
+};
+
+use crate::{random_soup, PeriodDetector, Region, Rule, SparseGrid, Stability, Universe};
+
+/// The outcomes a [Search] looks for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SearchTarget {
+    /// Every soup.
+    Any,
+    /// Soups which die out.
+    Extinct,
+    /// Soups which settle into a still life.
+    StillLife,
+    /// Soups which settle into an oscillator.
+    Oscillator,
+    /// Soups which settle into a spaceship.
+    #[default]
+    Spaceship,
+    /// Soups which have not settled after the most generations searched.
+    Unsettled,
+}
+
+impl SearchTarget {
+    /// Returns `true` if a soup which settled as `stability`, or did not settle if it is `None`, is a match.
+    pub fn matches(&self, stability: Option<Stability>) -> bool {
+        matches!(
+            (self, stability),
+            (SearchTarget::Any, _)
+                | (SearchTarget::Extinct, Some(Stability::Extinct))
+                | (SearchTarget::StillLife, Some(Stability::StillLife))
+                | (SearchTarget::Oscillator, Some(Stability::Oscillator { .. }))
+                | (SearchTarget::Spaceship, Some(Stability::Spaceship { .. }))
+                | (SearchTarget::Unsettled, None)
+        )
+    }
+}
+
+impl FromStr for SearchTarget {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "any" => Ok(SearchTarget::Any),
+            "extinct" => Ok(SearchTarget::Extinct),
+            "still-life" | "still" => Ok(SearchTarget::StillLife),
+            "oscillator" => Ok(SearchTarget::Oscillator),
+            "spaceship" => Ok(SearchTarget::Spaceship),
+            "unsettled" => Ok(SearchTarget::Unsettled),
+            _ => Err(s.to_string()),
+        }
+    }
+}
+
+impl Display for SearchTarget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            SearchTarget::Any => "any",
+            SearchTarget::Extinct => "extinct",
+            SearchTarget::StillLife => "still-life",
+            SearchTarget::Oscillator => "oscillator",
+            SearchTarget::Spaceship => "spaceship",
+            SearchTarget::Unsettled => "unsettled",
+        };
+        write!(f, "{name}")
+    }
+}
+
+/// What became of one random soup.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SoupResult {
+    /// The seed the soup was generated from.
+    pub seed: u64,
+    /// How the soup behaved once it settled, or `None` if it had not settled.
+    pub stability: Option<Stability>,
+    /// The generation the soup settled at, or the most generations searched if it did not.
+    pub generations: u64,
+    /// The number of live cells in the last generation.
+    pub population: u64,
+}
+
+/// Runs random soups on an unbounded plane until they settle, looking for ones with a chosen outcome.
+///
+/// # Example
+/// ```
+/// use game_of_life::{Search, SearchTarget, Stability};
+///
+/// let search = Search::new().with_seed(1).with_trials(20).with_target(SearchTarget::Any);
+/// let results: Vec<_> = search.results().collect();
+/// assert_eq!(results.len(), 20);
+/// assert!(results.iter().all(|result| result.generations <= 1000));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Search {
+    rule: Rule,
+    /// The size of the area filled by each soup.
+    width: i64,
+    height: i64,
+    density: f64,
+    /// The seed of the first soup, after which seeds count upwards.
+    seed: u64,
+    trials: u64,
+    /// The most generations each soup is run for.
+    generations: u64,
+    target: SearchTarget,
+}
+
+impl Default for Search {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Search {
+    /// Creates a search of 100 soups of 16 by 16 cells at density `0.35`, run for at most 1000 generations each.
+    pub fn new() -> Self {
+        Self {
+            rule: Rule::CONWAY,
+            width: 16,
+            height: 16,
+            density: 0.35,
+            seed: 0,
+            trials: 100,
+            generations: 1000,
+            target: SearchTarget::default(),
+        }
+    }
+
+    /// Sets the rule, which must not cause cells with no live neighbours to be born.
+    pub fn with_rule(mut self, rule: Rule) -> Self {
+        self.rule = rule;
+        self
+    }
+
+    /// Sets the size of the area filled by each soup.
+    pub fn with_size(mut self, width: u16, height: u16) -> Self {
+        self.width = width as i64;
+        self.height = height as i64;
+        self
+    }
+
+    /// Sets the probability of each cell in a soup being alive, between `0` and `1`.
+    pub fn with_density(mut self, density: f64) -> Self {
+        self.density = density;
+        self
+    }
+
+    /// Sets the seed of the first soup.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Sets the number of soups tried.
+    pub fn with_trials(mut self, trials: u64) -> Self {
+        self.trials = trials;
+        self
+    }
+
+    /// Sets the most generations each soup is run for.
+    pub fn with_generations(mut self, generations: u64) -> Self {
+        self.generations = generations;
+        self
+    }
+
+    /// Sets the outcome looked for.
+    pub fn with_target(mut self, target: SearchTarget) -> Self {
+        self.target = target;
+        self
+    }
+
+    /// Returns the outcome looked for.
+    pub fn target(&self) -> SearchTarget {
+        self.target
+    }
+
+    /// Runs a single soup until it settles or the most generations have passed.
+    pub fn run_soup(&self, seed: u64) -> SoupResult {
+        let region = Region {
+            x: 0,
+            y: 0,
+            width: self.width,
+            height: self.height,
+        };
+        let mut universe = SparseGrid::new(random_soup(seed, self.density, region), self.rule);
+        let mut detector = PeriodDetector::new();
+
+        let mut generation = 0;
+        let stability = loop {
+            if let Some(stability) = detector.observe(generation, &universe.live_cells()) {
+                break Some(stability);
+            }
+            if generation >= self.generations {
+                break None;
+            }
+            universe.step_forward();
+            generation += 1;
+        };
+
+        SoupResult {
+            seed,
+            stability,
+            generations: generation,
+            population: universe.population(),
+        }
+    }
+
+    /// Returns an iterator which runs each soup in turn, returning those which match the target.
+    pub fn results(&self) -> impl Iterator<Item = SoupResult> + '_ {
+        (self.seed..self.seed.saturating_add(self.trials))
+            .map(|seed| self.run_soup(seed))
+            .filter(|result| self.target.matches(result.stability))
+    }
+}
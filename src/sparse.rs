@@ -0,0 +1,124 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::{CoordinateError, Rule, Universe};
+
+/// An unbounded universe which stores only the coordinates of live cells.
+///
+/// Memory usage scales with the population rather than the area of the pattern, so patterns may grow indefinitely.
+#[derive(Clone, PartialEq)]
+pub struct SparseGrid {
+    cells: HashSet<(i64, i64)>,
+    rule: Rule,
+}
+
+impl SparseGrid {
+    /// Creates a sparse grid with the given cells alive.
+    ///
+    /// The rule must not cause cells with no live neighbours to be born, since that would fill the infinite plane. Cells
+    /// beyond the largest coordinates are never born.
+    ///
+    /// # Example
+    /// A glider keeps travelling well beyond the limits of a [Grid](crate::Grid):
+    /// ```
+    /// use game_of_life::{Rule, SparseGrid, Universe};
+    ///
+    /// let glider = vec![(1, 2), (2, 1), (0, 0), (1, 0), (2, 0)];
+    /// let mut grid = SparseGrid::new(glider.clone(), Rule::CONWAY);
+    /// for _ in 0..4 * 300 {
+    ///     grid.step_forward();
+    /// }
+    ///
+    /// let mut cells = grid.live_cells();
+    /// cells.sort();
+    /// let mut expected: Vec<(i64, i64)> = glider.iter().map(|&(x, y)| (x + 300, y - 300)).collect();
+    /// expected.sort();
+    /// assert_eq!(cells, expected);
+    /// ```
+    pub fn new(starting_cells: Vec<(i64, i64)>, rule: Rule) -> Self {
+        Self {
+            cells: starting_cells.into_iter().collect(),
+            rule,
+        }
+    }
+}
+
+impl Universe for SparseGrid {
+    /// Updates each live cell and its neighbours according to the grid's [Rule].
+    fn step_forward(&mut self) {
+        let mut neighbour_counts: HashMap<(i64, i64), u8> = HashMap::new();
+
+        for &(x, y) in &self.cells {
+            neighbour_counts.entry((x, y)).or_default();
+            for dx in -1..=1 {
+                for dy in -1..=1 {
+                    if dx == 0 && dy == 0 {
+                        continue;
+                    }
+                    if let (Some(i), Some(j)) = (x.checked_add(dx), y.checked_add(dy)) {
+                        *neighbour_counts.entry((i, j)).or_default() += 1;
+                    }
+                }
+            }
+        }
+
+        let next_cells = neighbour_counts
+            .into_iter()
+            .filter(|&(point, count)| {
+                if self.cells.contains(&point) {
+                    self.rule.survives(count)
+                } else {
+                    self.rule.is_born(count)
+                }
+            })
+            .map(|(point, _)| point)
+            .collect();
+
+        self.cells = next_cells;
+    }
+
+    fn is_alive(&self, x: i64, y: i64) -> bool {
+        self.cells.contains(&(x, y))
+    }
+
+    fn set_alive(&mut self, x: i64, y: i64, alive: bool) -> Result<(), CoordinateError> {
+        if alive {
+            self.cells.insert((x, y));
+        } else {
+            self.cells.remove(&(x, y));
+        }
+        Ok(())
+    }
+
+    fn population(&self) -> u64 {
+        self.cells.len() as u64
+    }
+
+    fn live_cells(&self) -> Vec<(i64, i64)> {
+        self.cells.iter().copied().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cells_at_the_largest_coordinates_keep_evolving() {
+        let blinker = vec![(i64::MAX, -1), (i64::MAX, 0), (i64::MAX, 1)];
+        let mut grid = SparseGrid::new(blinker, Rule::CONWAY);
+        grid.step_forward();
+        let mut cells = grid.live_cells();
+        cells.sort();
+        assert_eq!(cells, vec![(i64::MAX - 1, 0), (i64::MAX, 0)]);
+
+        let block = vec![
+            (i64::MIN, i64::MIN),
+            (i64::MIN + 1, i64::MIN),
+            (i64::MIN, i64::MIN + 1),
+            (i64::MIN + 1, i64::MIN + 1),
+        ];
+        let mut grid = SparseGrid::new(block, Rule::CONWAY);
+        grid.step_forward();
+        assert_eq!(grid.population(), 4);
+    }
+}
@@ -0,0 +1,238 @@
+use std::{
+    collections::HashSet,
+    fmt::{self, Display},
+    fs::File,
+    io::{self, BufWriter, Write},
+    path::Path,
+    str::FromStr,
+};
+
+use crate::{Recorder, Region, Universe};
+
+/// A file format which statistics can be written in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum StatsFormat {
+    /// Comma-separated values with a header row, usually with a `.csv` extension.
+    #[default]
+    Csv,
+    /// One JSON object per generation on each line, usually with a `.jsonl` extension.
+    JsonLines,
+}
+
+impl StatsFormat {
+    /// Returns the format with the given file extension, if any.
+    pub fn from_extension(extension: &str) -> Option<Self> {
+        match extension.to_ascii_lowercase().as_str() {
+            "csv" => Some(StatsFormat::Csv),
+            "jsonl" | "ndjson" | "json" => Some(StatsFormat::JsonLines),
+            _ => None,
+        }
+    }
+
+    /// Returns the format chosen by a file's extension, defaulting to CSV.
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Self {
+        path.as_ref()
+            .extension()
+            .and_then(|extension| StatsFormat::from_extension(&extension.to_string_lossy()))
+            .unwrap_or_default()
+    }
+}
+
+impl FromStr for StatsFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        StatsFormat::from_extension(s).ok_or_else(|| s.to_string())
+    }
+}
+
+impl Display for StatsFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            StatsFormat::Csv => "csv",
+            StatsFormat::JsonLines => "jsonl",
+        };
+        write!(f, "{name}")
+    }
+}
+
+/// Measurements of a single generation.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GenerationStats {
+    pub generation: u64,
+    /// The number of live cells.
+    pub population: u64,
+    /// The number of cells which came alive since the previous generation.
+    pub births: u64,
+    /// The number of cells which died since the previous generation.
+    pub deaths: u64,
+    /// The smallest region containing every live cell, or `None` if there are none.
+    pub bounding_box: Option<Region>,
+    /// The fraction of the bounding box which is alive, or `0` if there are no live cells.
+    pub density: f64,
+}
+
+impl GenerationStats {
+    const CSV_HEADER: &'static str =
+        "generation,population,births,deaths,min_x,min_y,width,height,density";
+
+    fn write_csv<W: Write>(&self, output: &mut W) -> io::Result<()> {
+        let bounds = match self.bounding_box {
+            Some(region) => format!(
+                "{},{},{},{}",
+                region.x, region.y, region.width, region.height
+            ),
+            None => String::from(",,0,0"),
+        };
+        writeln!(
+            output,
+            "{},{},{},{},{bounds},{:.6}",
+            self.generation, self.population, self.births, self.deaths, self.density
+        )
+    }
+
+    fn write_json<W: Write>(&self, output: &mut W) -> io::Result<()> {
+        let bounds = match self.bounding_box {
+            Some(region) => format!(
+                r#"{{"x":{},"y":{},"width":{},"height":{}}}"#,
+                region.x, region.y, region.width, region.height
+            ),
+            None => String::from("null"),
+        };
+        writeln!(
+            output,
+            r#"{{"generation":{},"population":{},"births":{},"deaths":{},"bounding_box":{bounds},"density":{:.6}}}"#,
+            self.generation, self.population, self.births, self.deaths, self.density
+        )
+    }
+}
+
+/// Measures the population, births, deaths, bounding box and density of each generation of a universe, writing a row
+/// for each to CSV or JSON lines.
+///
+/// Births and deaths are counted against the previous generation measured, so the first generation has none.
+///
+/// # Example
+/// ```
+/// use game_of_life::{Region, Rule, SparseGrid, StatsFormat, StatsRecorder, Universe};
+///
+/// let mut grid = SparseGrid::new(vec![(0, 1), (1, 1), (2, 1)], Rule::CONWAY);
+/// let mut output = Vec::new();
+///
+/// let mut recorder = StatsRecorder::new(&mut output, StatsFormat::Csv);
+/// for generation in 0..2 {
+///     let stats = recorder.add_frame(&grid, generation).unwrap();
+///     assert_eq!(stats.population, 3);
+///     grid.step_forward();
+/// }
+/// recorder.finish().unwrap();
+///
+/// let csv = String::from_utf8(output).unwrap();
+/// let rows: Vec<&str> = csv.lines().collect();
+/// assert_eq!(rows[0], "generation,population,births,deaths,min_x,min_y,width,height,density");
+/// assert_eq!(rows[1], "0,3,0,0,0,1,3,1,1.000000");
+/// assert_eq!(rows[2], "1,3,2,2,1,0,1,3,1.000000");
+/// ```
+pub struct StatsRecorder<W: Write> {
+    output: W,
+    format: StatsFormat,
+    /// The live cells of the previous generation measured, if any.
+    previous: Option<HashSet<(i64, i64)>>,
+}
+
+impl StatsRecorder<BufWriter<File>> {
+    /// Creates a file which statistics are written to, in a format chosen by its extension.
+    pub fn create<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let format = StatsFormat::from_path(&path);
+        Self::create_with_format(path, format)
+    }
+
+    /// Creates a file which statistics are written to in `format`.
+    pub fn create_with_format<P: AsRef<Path>>(path: P, format: StatsFormat) -> io::Result<Self> {
+        Ok(Self::new(BufWriter::new(File::create(path)?), format))
+    }
+}
+
+impl<W: Write> StatsRecorder<W> {
+    /// Creates a recorder which writes statistics to `output` in `format`.
+    pub fn new(output: W, format: StatsFormat) -> Self {
+        Self {
+            output,
+            format,
+            previous: None,
+        }
+    }
+
+    /// Measures the current state of a universe, writes it as the next row and returns it.
+    pub fn add_frame<U: Universe + ?Sized>(
+        &mut self,
+        universe: &U,
+        generation: u64,
+    ) -> io::Result<GenerationStats> {
+        let cells: HashSet<(i64, i64)> = universe.live_cells().into_iter().collect();
+        let (births, deaths) = match &self.previous {
+            Some(previous) => (
+                cells.difference(previous).count() as u64,
+                previous.difference(&cells).count() as u64,
+            ),
+            None => (0, 0),
+        };
+        let bounding_box = bounding_box(&cells);
+        let density = match bounding_box {
+            Some(region) => cells.len() as f64 / (region.width * region.height) as f64,
+            None => 0.0,
+        };
+        let stats = GenerationStats {
+            generation,
+            population: cells.len() as u64,
+            births,
+            deaths,
+            bounding_box,
+            density,
+        };
+
+        match self.format {
+            StatsFormat::Csv => {
+                if self.previous.is_none() {
+                    writeln!(self.output, "{}", GenerationStats::CSV_HEADER)?;
+                }
+                stats.write_csv(&mut self.output)?;
+            }
+            StatsFormat::JsonLines => stats.write_json(&mut self.output)?,
+        }
+        self.previous = Some(cells);
+        Ok(stats)
+    }
+
+    /// Flushes any statistics which have not yet been written.
+    pub fn finish(mut self) -> io::Result<()> {
+        self.output.flush()
+    }
+}
+
+impl<W: Write> Recorder for StatsRecorder<W> {
+    fn add_frame(&mut self, universe: &dyn Universe, generation: u64) -> io::Result<()> {
+        StatsRecorder::add_frame(self, universe, generation).map(|_| ())
+    }
+
+    fn finish(self: Box<Self>) -> io::Result<()> {
+        StatsRecorder::finish(*self)
+    }
+}
+
+/// Returns the smallest region containing every cell, or `None` if there are none.
+pub(crate) fn bounding_box<'a>(cells: impl IntoIterator<Item = &'a (i64, i64)>) -> Option<Region> {
+    let mut cells = cells.into_iter();
+    let &(x, y) = cells.next()?;
+    let (min_x, min_y, max_x, max_y) =
+        cells.fold((x, y, x, y), |(min_x, min_y, max_x, max_y), &(x, y)| {
+            (min_x.min(x), min_y.min(y), max_x.max(x), max_y.max(y))
+        });
+
+    Some(Region {
+        x: min_x,
+        y: min_y,
+        width: max_x - min_x + 1,
+        height: max_y - min_y + 1,
+    })
+}
@@ -0,0 +1,331 @@
+use std::{
+    fmt::Write as _,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use crate::{age_color, CellAge, Color, ImageStyle, Recorder, Region, Universe};
+
+/// The spacing between labelled coordinates, from which the smallest which leaves enough room is chosen.
+const LABEL_STEPS: [i64; 9] = [1, 2, 5, 10, 20, 50, 100, 200, 500];
+/// The least space between the centres of two labels, in pixels.
+const MIN_LABEL_SPACING: u32 = 24;
+
+/// The appearance of cells drawn by an [SvgStyle].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SvgStyle {
+    /// The width and height of each cell.
+    cell_size: u32,
+    alive: Color,
+    dead: Color,
+    /// The colour of lines drawn between cells, if any.
+    gridlines: Option<Color>,
+    /// Whether coordinates are labelled along the left and bottom edges.
+    labels: bool,
+    /// Whether live cells are coloured by age instead of with the alive colour.
+    age_colors: bool,
+}
+
+impl Default for SvgStyle {
+    fn default() -> Self {
+        Self {
+            cell_size: 10,
+            alive: Color::BLACK,
+            dead: Color::WHITE,
+            gridlines: None,
+            labels: false,
+            age_colors: false,
+        }
+    }
+}
+
+impl SvgStyle {
+    /// Sets the width and height of each cell, clamped between 1 and [ImageStyle::MAX_CELL_SIZE] like the cells of a
+    /// raster image.
+    pub fn with_cell_size(mut self, cell_size: u32) -> Self {
+        self.cell_size = cell_size.clamp(1, ImageStyle::MAX_CELL_SIZE);
+        self
+    }
+
+    pub fn with_colors(mut self, alive: Color, dead: Color) -> Self {
+        self.alive = alive;
+        self.dead = dead;
+        self
+    }
+
+    pub fn cell_size(&self) -> u32 {
+        self.cell_size
+    }
+
+    /// Draws lines of the given colour between cells.
+    pub fn with_gridlines(mut self, gridlines: Option<Color>) -> Self {
+        self.gridlines = gridlines;
+        self
+    }
+
+    /// Labels coordinates along the left and bottom edges.
+    pub fn with_labels(mut self, labels: bool) -> Self {
+        self.labels = labels;
+        self
+    }
+
+    /// Colours live cells by age, for universes which track it, in the same colours as the terminal.
+    pub fn with_age_colors(mut self, age_colors: bool) -> Self {
+        self.age_colors = age_colors;
+        self
+    }
+
+    /// Renders `region` of a universe as an SVG image, with `y` increasing upwards.
+    ///
+    /// # Example
+    /// ```
+    /// use game_of_life::{Region, Rule, SparseGrid, SvgStyle};
+    ///
+    /// let grid = SparseGrid::new(vec![(0, 0)], Rule::CONWAY);
+    /// let svg = SvgStyle::default().render(&grid, Region { x: 0, y: 0, width: 2, height: 2 });
+    ///
+    /// assert!(svg.starts_with("<svg"));
+    /// assert!(svg.contains(r#"<rect x="0" y="10" width="10" height="10"/>"#));
+    /// ```
+    pub fn render<U: Universe + ?Sized>(&self, universe: &U, region: Region) -> String {
+        let (width, height) = self.frame_size(region, false);
+        let mut svg = header(width, height);
+        self.write_frame(&mut svg, universe, region, (0, 0), None);
+        svg.push_str("</svg>\n");
+        svg
+    }
+
+    /// Returns the font size of labels and captions.
+    fn font_size(&self) -> u32 {
+        (self.cell_size * 3 / 4).clamp(8, 16)
+    }
+
+    /// Returns the space left of the cells for labels, and beneath them for labels and above them for a caption.
+    fn margins(&self, captioned: bool) -> (u32, u32, u32) {
+        let font_size = self.font_size();
+        let left = if self.labels { font_size * 3 } else { 0 };
+        let bottom = if self.labels { font_size * 3 / 2 } else { 0 };
+        let top = if captioned { font_size * 2 } else { 0 };
+        (left, bottom, top)
+    }
+
+    /// Returns the size of a frame of `region`, including its labels and caption.
+    fn frame_size(&self, region: Region, captioned: bool) -> (u32, u32) {
+        let (left, bottom, top) = self.margins(captioned);
+        (
+            left + region.width.max(0) as u32 * self.cell_size,
+            top + region.height.max(0) as u32 * self.cell_size + bottom,
+        )
+    }
+
+    /// Writes a frame of `region` with its top-left corner at `origin`, captioned with a generation number if given.
+    fn write_frame<U: Universe + ?Sized>(
+        &self,
+        svg: &mut String,
+        universe: &U,
+        region: Region,
+        origin: (u32, u32),
+        generation: Option<u64>,
+    ) {
+        let (left, _, top) = self.margins(generation.is_some());
+        let size = self.cell_size;
+        let (cells_width, cells_height) = (
+            region.width.max(0) as u32 * size,
+            region.height.max(0) as u32 * size,
+        );
+        let font_size = self.font_size();
+
+        let _ = writeln!(
+            svg,
+            r#"<g transform="translate({},{})" font-family="sans-serif" font-size="{font_size}">"#,
+            origin.0, origin.1
+        );
+        if let Some(generation) = generation {
+            let _ = writeln!(
+                svg,
+                r#"<text x="{}" y="{}" text-anchor="middle">Generation {generation}</text>"#,
+                left + cells_width / 2,
+                font_size * 3 / 2
+            );
+        }
+        let _ = writeln!(svg, r#"<g transform="translate({left},{top})">"#);
+        let _ = writeln!(
+            svg,
+            r#"<rect width="{cells_width}" height="{cells_height}" fill="{}"/>"#,
+            self.dead
+        );
+
+        let mut cells = universe.live_cells();
+        cells.retain(|&(x, y)| region.contains(x, y));
+        cells.sort_by_key(|&(x, y)| (-y, x));
+
+        let _ = writeln!(svg, r#"<g fill="{}">"#, self.alive);
+        for (x, y) in cells {
+            let cell_x = (x - region.x) as u32 * size;
+            let cell_y = (region.y + region.height - 1 - y) as u32 * size;
+            let fill = match universe.cell_age(x, y) {
+                Some(CellAge::Alive(age)) if self.age_colors => {
+                    let (red, green, blue) = age_color(age);
+                    format!(r#" fill="{}""#, Color::new(red, green, blue))
+                }
+                _ => String::new(),
+            };
+            let _ = writeln!(
+                svg,
+                r#"<rect x="{cell_x}" y="{cell_y}" width="{size}" height="{size}"{fill}/>"#
+            );
+        }
+        svg.push_str("</g>\n");
+
+        if let Some(color) = self.gridlines {
+            let mut path = String::new();
+            for column in 0..=region.width.max(0) as u32 {
+                let _ = write!(path, "M{} 0V{cells_height}", column * size);
+            }
+            for row in 0..=region.height.max(0) as u32 {
+                let _ = write!(path, "M0 {}H{cells_width}", row * size);
+            }
+            let _ = writeln!(
+                svg,
+                r#"<path d="{path}" stroke="{color}" stroke-width="{}" fill="none"/>"#,
+                (size as f64 / 20.0).max(0.5)
+            );
+        }
+
+        if self.labels {
+            let step = LABEL_STEPS
+                .into_iter()
+                .find(|&step| step as u32 * size >= MIN_LABEL_SPACING)
+                .unwrap_or(1000);
+            let label_y = cells_height + font_size * 5 / 4;
+            for x in (region.x..region.x + region.width).filter(|x| x.rem_euclid(step) == 0) {
+                let _ = writeln!(
+                    svg,
+                    r#"<text x="{}" y="{label_y}" text-anchor="middle">{x}</text>"#,
+                    (x - region.x) as u32 * size + size / 2
+                );
+            }
+            for y in (region.y..region.y + region.height).filter(|y| y.rem_euclid(step) == 0) {
+                let _ = writeln!(
+                    svg,
+                    r#"<text x="-4" y="{}" text-anchor="end" dominant-baseline="middle">{y}</text>"#,
+                    (region.y + region.height - 1 - y) as u32 * size + size / 2
+                );
+            }
+        }
+        svg.push_str("</g>\n</g>\n");
+    }
+}
+
+fn header(width: u32, height: u32) -> String {
+    format!(
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}" viewBox="0 0 {width} {height}">"#
+    ) + "\n"
+}
+
+/// Collects frames of a universe into an SVG sheet, with the generations laid out in rows side by side.
+///
+/// # Example
+/// A blinker alternates between horizontal and vertical:
+/// ```
+/// use game_of_life::{Region, Rule, SparseGrid, SvgSheet, SvgStyle, Universe};
+///
+/// let mut grid = SparseGrid::new(vec![(0, 1), (1, 1), (2, 1)], Rule::CONWAY);
+/// let mut sheet = SvgSheet::new(SvgStyle::default(), Region { x: 0, y: 0, width: 3, height: 3 }, 3);
+/// for generation in 0..3 {
+///     sheet.add_frame(&grid, generation);
+///     grid.step_forward();
+/// }
+///
+/// let svg = sheet.to_svg();
+/// assert!(svg.contains("Generation 0") && svg.contains("Generation 2"));
+/// assert_eq!(svg.matches("<g transform").count(), 6);
+/// ```
+pub struct SvgSheet {
+    style: SvgStyle,
+    region: Region,
+    /// The number of frames in each row, where `1` is a vertical strip.
+    columns: usize,
+    /// The most frames which are kept, after which later frames are ignored.
+    max_frames: Option<usize>,
+    frames: String,
+    frame_count: usize,
+    /// The file the sheet is written to when it is [finished](Recorder::finish).
+    path: Option<PathBuf>,
+}
+
+impl SvgSheet {
+    /// Creates an empty sheet of frames of `region`, with `columns` frames in each row.
+    pub fn new(style: SvgStyle, region: Region, columns: usize) -> Self {
+        Self {
+            style,
+            region,
+            columns: columns.max(1),
+            max_frames: None,
+            frames: String::new(),
+            frame_count: 0,
+            path: None,
+        }
+    }
+
+    /// Ignores any frames after the first `max_frames`.
+    pub fn with_max_frames(mut self, max_frames: usize) -> Self {
+        self.max_frames = Some(max_frames);
+        self
+    }
+
+    /// Sets the file the sheet is written to when used as a [Recorder].
+    pub fn with_path<P: AsRef<Path>>(mut self, path: P) -> Self {
+        self.path = Some(path.as_ref().to_path_buf());
+        self
+    }
+
+    /// Adds a frame showing the current state of a universe, captioned with `generation`.
+    pub fn add_frame<U: Universe + ?Sized>(&mut self, universe: &U, generation: u64) {
+        if self.max_frames.is_some_and(|max| self.frame_count >= max) {
+            return;
+        }
+        let (width, height) = self.frame_spacing();
+        let column = (self.frame_count % self.columns) as u32;
+        let row = (self.frame_count / self.columns) as u32;
+        let origin = (column * width, row * height);
+
+        let mut frame = String::new();
+        self.style
+            .write_frame(&mut frame, universe, self.region, origin, Some(generation));
+        self.frames.push_str(&frame);
+        self.frame_count += 1;
+    }
+
+    /// Returns the SVG image of every frame added so far.
+    pub fn to_svg(&self) -> String {
+        let (width, height) = self.frame_spacing();
+        let columns = self.columns.min(self.frame_count).max(1) as u32;
+        let rows = self.frame_count.div_ceil(self.columns).max(1) as u32;
+        let mut svg = header(columns * width, rows * height);
+        svg.push_str(&self.frames);
+        svg.push_str("</svg>\n");
+        svg
+    }
+
+    /// Returns the distance between the top-left corners of neighbouring frames.
+    fn frame_spacing(&self) -> (u32, u32) {
+        let (width, height) = self.style.frame_size(self.region, true);
+        let gap = self.style.cell_size * 2;
+        (width + gap, height + gap)
+    }
+}
+
+impl Recorder for SvgSheet {
+    fn add_frame(&mut self, universe: &dyn Universe, generation: u64) -> io::Result<()> {
+        SvgSheet::add_frame(self, universe, generation);
+        Ok(())
+    }
+
+    fn finish(self: Box<Self>) -> io::Result<()> {
+        match &self.path {
+            Some(path) => fs::write(path, self.to_svg()),
+            None => Ok(()),
+        }
+    }
+}
@@ -0,0 +1,19 @@
+//! Fixtures shared by unit tests.
+
+use crate::Topology;
+
+/// Every topology, for checking that engines behave the same on each.
+pub(crate) const TOPOLOGIES: [Topology; 4] = [
+    Topology::Bounded,
+    Topology::Horizontal,
+    Topology::Vertical,
+    Topology::Torus,
+];
+
+/// Returns an irregular but deterministic soup filling about 40% of a `width` by `height` grid.
+pub(crate) fn fixture_cells(width: u8, height: u8) -> Vec<(u8, u8)> {
+    (0..width)
+        .flat_map(|x| (0..height).map(move |y| (x, y)))
+        .filter(|&(x, y)| (x as u32 * 7 + y as u32 * 13) % 5 < 2)
+        .collect()
+}
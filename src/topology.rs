@@ -0,0 +1,54 @@
+use std::{
+    fmt::{self, Display},
+    str::FromStr,
+};
+
+/// Describes how the edges of a [Grid](crate::Grid) connect to each other.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Topology {
+    /// Cells beyond the edges are always dead.
+    #[default]
+    Bounded,
+    /// The left and right edges wrap around, forming a cylinder.
+    Horizontal,
+    /// The top and bottom edges wrap around, forming a cylinder.
+    Vertical,
+    /// Both pairs of edges wrap around, forming a torus.
+    Torus,
+}
+
+impl Topology {
+    pub fn wraps_horizontally(&self) -> bool {
+        matches!(self, Topology::Horizontal | Topology::Torus)
+    }
+
+    pub fn wraps_vertically(&self) -> bool {
+        matches!(self, Topology::Vertical | Topology::Torus)
+    }
+}
+
+impl FromStr for Topology {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "bounded" | "plane" => Ok(Topology::Bounded),
+            "horizontal" | "cylinder" => Ok(Topology::Horizontal),
+            "vertical" => Ok(Topology::Vertical),
+            "torus" => Ok(Topology::Torus),
+            _ => Err(s.to_string()),
+        }
+    }
+}
+
+impl Display for Topology {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Topology::Bounded => "bounded",
+            Topology::Horizontal => "horizontal",
+            Topology::Vertical => "vertical",
+            Topology::Torus => "torus",
+        };
+        write!(f, "{name}")
+    }
+}
@@ -0,0 +1,288 @@
+use std::{
+    io,
+    time::{Duration, Instant},
+};
+
+use crossterm::{
+    event::{
+        self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEvent, KeyEventKind,
+        KeyModifiers,
+    },
+    execute, terminal,
+};
+
+use crate::{Editor, EditorAction, Game, Glyphs, Recording};
+
+const DEFAULT_DELAY: Duration = Duration::from_millis(100);
+const MIN_DELAY: Duration = Duration::from_millis(5);
+const MAX_DELAY: Duration = Duration::from_secs(2);
+/// The number of characters the view moves when panning.
+const PAN_STEP: i64 = 4;
+/// The number of lines of status text drawn beneath the editor.
+const EDITOR_FOOTER_LINES: usize = 2;
+/// How long to wait for input while paused before checking again.
+const PAUSED_POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Keeps the terminal in raw mode until dropped.
+struct RawMode;
+
+impl RawMode {
+    fn enable() -> io::Result<Self> {
+        terminal::enable_raw_mode()?;
+        Ok(Self)
+    }
+}
+
+impl Drop for RawMode {
+    fn drop(&mut self) {
+        let _ = execute!(io::stdout(), DisableMouseCapture);
+        let _ = terminal::disable_raw_mode();
+    }
+}
+
+/// An interactive terminal session controlling a [Game].
+///
+/// * `space` - Pause or resume
+/// * `n` - Step forward one generation
+/// * `+`/`-` - Speed up or slow down
+/// * `r` - Reset to the starting pattern
+/// * Arrow keys - Pan the view
+/// * `[`/`]` - Zoom out or in
+/// * `f` - Toggle keeping the pattern centred in the view
+/// * `e` - Edit the current cells with an [Editor]
+/// * `q`, `esc` or `ctrl-c` - Quit
+pub struct Tui<'a> {
+    game: &'a mut Game,
+    /// The recording each generation is added to, if any.
+    recording: Option<&'a mut Recording>,
+    /// The generation to pause at, or `0` to run indefinitely.
+    cycle_count: u64,
+    delay: Duration,
+    paused: bool,
+    /// The editor, while the cells are being edited rather than simulated.
+    editor: Option<Editor>,
+    /// Whether to pause once the pattern has settled.
+    pause_when_settled: bool,
+    /// Whether the pattern had settled when last drawn, so that it is only paused when it first settles.
+    was_settled: bool,
+    /// The glyphs drawn before editing, which are restored afterwards since the editor always draws text.
+    glyphs: Glyphs,
+}
+
+impl<'a> Tui<'a> {
+    pub fn new(game: &'a mut Game, cycle_count: u64) -> Self {
+        Self {
+            game,
+            recording: None,
+            cycle_count,
+            delay: DEFAULT_DELAY,
+            paused: false,
+            editor: None,
+            pause_when_settled: false,
+            was_settled: false,
+            glyphs: Glyphs::default(),
+        }
+    }
+
+    /// Pauses once the game detects that the pattern has settled, after which it can still be stepped.
+    ///
+    /// This starts the game [detecting periods](Game::detect_periods), which also shows whether it has settled in the
+    /// status line.
+    pub fn pausing_when_settled(mut self) -> Self {
+        self.game.detect_periods();
+        self.pause_when_settled = true;
+        self
+    }
+
+    /// Sets the time between generations while running, which can be changed with `+` and `-`.
+    pub fn with_delay(mut self, delay: Duration) -> Self {
+        self.delay = delay.clamp(MIN_DELAY, MAX_DELAY);
+        self
+    }
+
+    /// Adds every generation the session steps through to `recording`.
+    pub fn with_recording(mut self, recording: &'a mut Recording) -> Self {
+        self.recording = Some(recording);
+        self
+    }
+
+    /// Starts the session in the editor rather than running the simulation.
+    pub fn editing(mut self) -> Self {
+        self.editor = Some(self.new_editor());
+        self
+    }
+
+    fn new_editor(&mut self) -> Editor {
+        let viewport = self.game.viewport_mut();
+        self.glyphs = viewport.glyphs();
+        viewport.set_glyphs(Glyphs::Text);
+        viewport.set_scale(1);
+        self.game.fit_viewport(EDITOR_FOOTER_LINES);
+        Editor::new(
+            self.game.live_cells().collect(),
+            self.game.view(),
+            self.game.rule(),
+        )
+        .with_save_path(self.game.save_path())
+    }
+
+    /// Runs the session until the user quits.
+    pub fn run(&mut self) -> io::Result<()> {
+        let _raw_mode = RawMode::enable()?;
+        if self.editor.is_some() {
+            execute!(io::stdout(), EnableMouseCapture)?;
+        }
+        let mut last_step = Instant::now();
+
+        loop {
+            if self.editor.is_some() {
+                if !self.edit()? {
+                    return Ok(());
+                }
+                last_step = Instant::now();
+                continue;
+            }
+
+            let settled = self.game.stability().is_some();
+            if self.pause_when_settled && settled && !self.was_settled {
+                self.paused = true;
+            }
+            self.was_settled = settled;
+            if self.cycle_count != 0 && self.game.generation() >= self.cycle_count {
+                self.paused = true;
+            }
+            self.draw()?;
+
+            let timeout = if self.paused {
+                PAUSED_POLL_INTERVAL
+            } else {
+                self.delay.saturating_sub(last_step.elapsed())
+            };
+
+            if event::poll(timeout)? {
+                if let Event::Key(key) = event::read()? {
+                    if !self.handle_key(key)? {
+                        return Ok(());
+                    }
+                }
+            } else if !self.paused {
+                self.advance()?;
+                last_step = Instant::now();
+            }
+        }
+    }
+
+    /// Advances the game by one generation and adds it to the recording.
+    fn advance(&mut self) -> io::Result<()> {
+        self.game.advance(1).map_err(io::Error::other)?;
+        if let Some(recording) = &mut self.recording {
+            recording.add_frame(self.game);
+        }
+        Ok(())
+    }
+
+    /// Draws the editor and handles one event, returning `false` if the session should end.
+    fn edit(&mut self) -> io::Result<bool> {
+        let editor = self.editor.as_mut().unwrap();
+        self.game.draw(&editor.lines())?;
+
+        let action = match event::read()? {
+            Event::Key(key)
+                if key.code == KeyCode::Char('c')
+                    && key.modifiers.contains(KeyModifiers::CONTROL) =>
+            {
+                EditorAction::Quit
+            }
+            Event::Key(key) => editor.handle_key(key),
+            Event::Mouse(mouse) => {
+                editor.handle_mouse(mouse);
+                EditorAction::Continue
+            }
+            _ => EditorAction::Continue,
+        };
+
+        match action {
+            EditorAction::Continue => Ok(true),
+            EditorAction::Start(cells) => {
+                execute!(io::stdout(), DisableMouseCapture)?;
+                self.editor = None;
+                self.game.viewport_mut().set_glyphs(self.glyphs);
+                self.game.set_starting_cells(cells);
+                self.paused = false;
+                Ok(true)
+            }
+            EditorAction::Quit => Ok(false),
+        }
+    }
+
+    /// Responds to a key press, returning `false` if the session should end.
+    fn handle_key(&mut self, key: KeyEvent) -> io::Result<bool> {
+        if key.kind != KeyEventKind::Press {
+            return Ok(true);
+        }
+
+        match key.code {
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                return Ok(false)
+            }
+            KeyCode::Char('q') | KeyCode::Esc => return Ok(false),
+            KeyCode::Char(' ') => self.paused = !self.paused,
+            KeyCode::Char('n') => self.advance()?,
+            KeyCode::Char('+') | KeyCode::Char('=') => {
+                self.delay = (self.delay / 2).max(MIN_DELAY);
+            }
+            KeyCode::Char('-') => self.delay = (self.delay * 2).min(MAX_DELAY),
+            KeyCode::Char('r') => self.game.reset(),
+            KeyCode::Left => self.game.viewport_mut().pan(-PAN_STEP, 0),
+            KeyCode::Right => self.game.viewport_mut().pan(PAN_STEP, 0),
+            KeyCode::Up => self.game.viewport_mut().pan(0, PAN_STEP),
+            KeyCode::Down => self.game.viewport_mut().pan(0, -PAN_STEP),
+            KeyCode::Char('[') => self.game.viewport_mut().zoom_out(),
+            KeyCode::Char(']') => self.game.viewport_mut().zoom_in(),
+            KeyCode::Char('f') => {
+                let viewport = self.game.viewport_mut();
+                viewport.set_following(!viewport.is_following());
+            }
+            KeyCode::Char('e') => {
+                let _ = execute!(io::stdout(), EnableMouseCapture);
+                self.editor = Some(self.new_editor());
+            }
+            _ => {}
+        }
+        Ok(true)
+    }
+
+    fn draw(&mut self) -> io::Result<()> {
+        let viewport = self.game.viewport();
+        let view = viewport.region();
+        let status = format!(
+            "Generation: {}  Population: {}  Rule: {}  Delay: {:?}  View: {},{} 1:{}{}  {}",
+            self.game.generation(),
+            self.game.population(),
+            self.game.rule(),
+            self.delay,
+            view.x,
+            view.y,
+            viewport.scale(),
+            if viewport.is_following() {
+                " following"
+            } else {
+                ""
+            },
+            if self.paused { "[paused]" } else { "" }
+        );
+        let help = "space: pause/resume  n: step  +/-: speed  arrows: pan  [/]: zoom  f: follow  r: reset  e: edit  q: quit"
+            .to_string();
+        // Whether the pattern has settled is only known when periods are being detected
+        let lines = if self.game.is_detecting_periods() {
+            let stability = match self.game.stability() {
+                Some(stability) => format!("Settled: {stability}"),
+                None => String::from("Not settled"),
+            };
+            vec![status, stability, help]
+        } else {
+            vec![status, help]
+        };
+        self.game.print_game_state_with_status(&lines)
+    }
+}
@@ -0,0 +1,184 @@
+use std::{
+    fmt::{self, Display},
+    str::FromStr,
+    thread,
+};
+
+/// A backend which stores and evolves a Game of Life pattern.
+///
+/// Coordinates are signed so that unbounded backends can grow in every direction; `0,0` is the bottom-left cell of
+/// a bounded grid.
+pub trait Universe {
+    /// Advances the universe by one generation.
+    fn step_forward(&mut self);
+
+    /// Advances the universe by `generations` generations, or returns a [JumpTooLarge] error and leaves it unchanged
+    /// if cells could move beyond the coordinates it can represent.
+    fn advance(&mut self, generations: u64) -> Result<(), JumpTooLarge> {
+        for _ in 0..generations {
+            self.step_forward();
+        }
+        Ok(())
+    }
+
+    /// Returns `true` if the cell at the given coordinates is alive.
+    fn is_alive(&self, x: i64, y: i64) -> bool;
+
+    /// Makes the cell at the given coordinates alive or dead, or returns a [CoordinateError] if they are outside a
+    /// fixed-size grid.
+    fn set_alive(&mut self, x: i64, y: i64, alive: bool) -> Result<(), CoordinateError>;
+
+    /// Returns the cells of a fixed-size grid, or `None` if the universe is unbounded.
+    fn bounds(&self) -> Option<Region> {
+        None
+    }
+
+    /// Returns the number of live cells.
+    fn population(&self) -> u64 {
+        self.live_cells().len() as u64
+    }
+
+    /// Returns the coordinates of every live cell.
+    fn live_cells(&self) -> Vec<(i64, i64)>;
+
+    /// Returns how long the cell at the given coordinates has been alive or dead, or `None` if the universe does not
+    /// track the ages of its cells.
+    fn cell_age(&self, _x: i64, _y: i64) -> Option<CellAge> {
+        None
+    }
+}
+
+/// How long a cell has been in its current state.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CellAge {
+    /// The cell has been alive for this many generations, where `0` means it was just born.
+    Alive(u32),
+    /// The cell died this many generations ago, where `0` means it just died.
+    Dead(u32),
+    /// The cell has never been alive.
+    NeverAlive,
+}
+
+/// A rectangular area of the grid, with `x,y` at its bottom-left corner.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Region {
+    pub x: i64,
+    pub y: i64,
+    pub width: i64,
+    pub height: i64,
+}
+
+impl Region {
+    /// Returns `true` if the cell at the given coordinates is inside the region.
+    ///
+    /// Coordinates too far from the region for their offset to fit in an `i64` are outside it.
+    pub fn contains(&self, x: i64, y: i64) -> bool {
+        let within =
+            |offset: Option<i64>, size| offset.is_some_and(|offset| (0..size).contains(&offset));
+        within(x.checked_sub(self.x), self.width) && within(y.checked_sub(self.y), self.height)
+    }
+}
+
+/// An error returned when coordinates point outside a fixed-size grid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CoordinateError {
+    pub x: i64,
+    pub y: i64,
+    /// The cells of the grid.
+    pub bounds: Region,
+}
+
+impl CoordinateError {
+    /// Returns an error if the cell at the given coordinates is outside `bounds`.
+    pub fn check(x: i64, y: i64, bounds: Region) -> Result<(), Self> {
+        if bounds.contains(x, y) {
+            Ok(())
+        } else {
+            Err(Self { x, y, bounds })
+        }
+    }
+}
+
+impl std::error::Error for CoordinateError {}
+
+impl Display for CoordinateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Cell {},{} is outside the {}x{} grid.",
+            self.x, self.y, self.bounds.width, self.bounds.height
+        )
+    }
+}
+
+/// An error returned when advancing a [Universe] could move cells beyond the coordinates it can represent.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct JumpTooLarge {
+    pub generations: u64,
+}
+
+impl std::error::Error for JumpTooLarge {}
+
+impl Display for JumpTooLarge {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Cannot advance by {} generations, as cells could move beyond the largest coordinates.",
+            self.generations
+        )
+    }
+}
+
+/// The backend used to store and evolve the game state.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Engine {
+    /// A fixed-size dense [Grid](crate::Grid).
+    #[default]
+    Grid,
+    /// A fixed-size bit-packed [PackedGrid](crate::PackedGrid), which evolves much faster than [Engine::Grid].
+    Packed,
+    /// An unbounded [SparseGrid](crate::SparseGrid) storing only live cells.
+    Sparse,
+    /// An unbounded [HashLife](crate::HashLife) quadtree which can skip ahead exponentially.
+    HashLife,
+}
+
+impl FromStr for Engine {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "grid" => Ok(Engine::Grid),
+            "packed" => Ok(Engine::Packed),
+            "sparse" => Ok(Engine::Sparse),
+            "hashlife" => Ok(Engine::HashLife),
+            _ => Err(s.to_string()),
+        }
+    }
+}
+
+impl Display for Engine {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Engine::Grid => "grid",
+            Engine::Packed => "packed",
+            Engine::Sparse => "sparse",
+            Engine::HashLife => "hashlife",
+        };
+        write!(f, "{name}")
+    }
+}
+
+/// Returns `threads`, or the number of available cores if `threads` is `0`.
+pub(crate) fn resolve_thread_count(threads: usize) -> usize {
+    if threads == 0 {
+        thread::available_parallelism().map_or(1, |cores| cores.get())
+    } else {
+        threads
+    }
+}
+
+/// Returns the number of rows in each band when splitting `height` rows between `threads` threads.
+pub(crate) fn rows_per_band(height: usize, threads: usize) -> usize {
+    height.div_ceil(threads.max(1)).max(1)
+}
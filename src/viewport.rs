@@ -0,0 +1,342 @@
+use std::{
+    collections::HashMap,
+    fmt::{self, Display},
+    str::FromStr,
+};
+
+use crate::{colored_frame_lines, frame_lines, ColorMode, Region, Universe};
+
+/// The glyphs used when zoomed out, from an empty block of cells to a full one.
+const SHADES: [char; 5] = ['.', '░', '▒', '▓', '█'];
+/// The bit of a Braille character for each dot, from the top row down.
+const BRAILLE_BITS: [[u32; 2]; 4] = [[0, 3], [1, 4], [2, 5], [6, 7]];
+const BRAILLE_BLANK: u32 = 0x2800;
+
+/// How cells are drawn as characters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Glyphs {
+    /// One cell per character, drawn as `O` or `.` and separated by spaces.
+    #[default]
+    Text,
+    /// Two cells per character, one above the other, drawn with half-block characters.
+    HalfBlock,
+    /// Eight cells per character, two across and four down, drawn with Braille patterns.
+    Braille,
+}
+
+impl Glyphs {
+    /// Returns the number of cells across and down each character, when not zoomed out.
+    pub fn cells_per_character(&self) -> (i64, i64) {
+        match self {
+            Glyphs::Text => (1, 1),
+            Glyphs::HalfBlock => (1, 2),
+            Glyphs::Braille => (2, 4),
+        }
+    }
+
+    /// Returns the number of terminal columns used by each character, including any separator.
+    fn columns_per_character(&self) -> i64 {
+        match self {
+            Glyphs::Text => 2,
+            Glyphs::HalfBlock | Glyphs::Braille => 1,
+        }
+    }
+}
+
+impl FromStr for Glyphs {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "text" => Ok(Glyphs::Text),
+            "half" | "halfblock" => Ok(Glyphs::HalfBlock),
+            "braille" => Ok(Glyphs::Braille),
+            _ => Err(s.to_string()),
+        }
+    }
+}
+
+impl Display for Glyphs {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Glyphs::Text => "text",
+            Glyphs::HalfBlock => "halfblock",
+            Glyphs::Braille => "braille",
+        };
+        write!(f, "{name}")
+    }
+}
+
+/// The part of a universe which is drawn, which can be panned, zoomed out and made to follow the pattern.
+///
+/// Each character drawn represents one or more dots, depending on its [Glyphs], and each dot represents a square of
+/// `scale` by `scale` cells. Text dots are shaded by how many of their cells are alive, while other dots are drawn
+/// if any of their cells are alive.
+///
+/// # Example
+/// ```
+/// use game_of_life::{Glyphs, Region, Rule, SparseGrid, Viewport};
+///
+/// let grid = SparseGrid::new(vec![(0, 0), (1, 0), (0, 1), (1, 1), (3, 3)], Rule::CONWAY);
+/// let mut viewport = Viewport::new(Region { x: 0, y: 0, width: 4, height: 4 }, true);
+/// assert_eq!(viewport.lines(&grid), [". . . O", ". . . .", "O O . .", "O O . ."]);
+///
+/// viewport.zoom_out();
+/// assert_eq!(viewport.lines(&grid), [". ░", "█ ."]);
+///
+/// viewport.zoom_in();
+/// viewport.set_glyphs(Glyphs::HalfBlock);
+/// assert_eq!(viewport.lines(&grid), ["   ▀", "██  "]);
+///
+/// viewport.set_glyphs(Glyphs::Braille);
+/// assert_eq!(viewport.lines(&grid), ["⣤⠈"]);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Viewport {
+    /// The cell at the bottom-left corner of the view.
+    x: i64,
+    y: i64,
+    /// The size of the view in cells, before it is fitted to the terminal.
+    width: i64,
+    height: i64,
+    /// The number of terminal columns and rows available, if known.
+    limit: Option<(i64, i64)>,
+    /// The number of cells along each side of the square drawn by one dot.
+    scale: i64,
+    glyphs: Glyphs,
+    /// How cells are coloured, which only applies to text which is not zoomed out.
+    colors: ColorMode,
+    /// The edges of a fixed-size grid, which the view is kept within.
+    bounds: Option<Region>,
+    /// Whether the view is kept centred on the pattern.
+    follow: bool,
+}
+
+impl Viewport {
+    /// The most cells along each side of the square drawn by one dot.
+    pub const MAX_SCALE: i64 = 1 << 20;
+
+    /// Creates a viewport showing `region` as text, keeping within it if `bounded` is `true`.
+    pub fn new(region: Region, bounded: bool) -> Self {
+        Self {
+            x: region.x,
+            y: region.y,
+            width: region.width,
+            height: region.height,
+            limit: None,
+            scale: 1,
+            glyphs: Glyphs::default(),
+            colors: ColorMode::default(),
+            bounds: bounded.then_some(region),
+            follow: false,
+        }
+    }
+
+    /// Returns the number of characters across and down the view.
+    pub fn size(&self) -> (i64, i64) {
+        let (cell_width, cell_height) = self.character_size();
+        let (width, height) = match self.bounds {
+            Some(bounds) => (bounds.width, bounds.height),
+            None => (self.width * self.scale, self.height * self.scale),
+        };
+        let mut columns = (width + cell_width - 1) / cell_width;
+        let mut rows = (height + cell_height - 1) / cell_height;
+        if let Some((terminal_columns, terminal_rows)) = self.limit {
+            columns = columns.min((terminal_columns + 1) / self.glyphs.columns_per_character());
+            rows = rows.min(terminal_rows);
+        }
+        (columns.max(1), rows.max(1))
+    }
+
+    /// Returns the cells covered by the view.
+    pub fn region(&self) -> Region {
+        let (columns, rows) = self.size();
+        let (cell_width, cell_height) = self.character_size();
+        Region {
+            x: self.x,
+            y: self.y,
+            width: columns * cell_width,
+            height: rows * cell_height,
+        }
+    }
+
+    /// Returns the number of cells across and down each character.
+    fn character_size(&self) -> (i64, i64) {
+        let (dots_across, dots_down) = self.glyphs.cells_per_character();
+        (dots_across * self.scale, dots_down * self.scale)
+    }
+
+    pub fn scale(&self) -> i64 {
+        self.scale
+    }
+
+    pub fn is_following(&self) -> bool {
+        self.follow
+    }
+
+    pub fn glyphs(&self) -> Glyphs {
+        self.glyphs
+    }
+
+    /// Sets how cells are drawn, keeping the centre of the view in place.
+    pub fn set_glyphs(&mut self, glyphs: Glyphs) {
+        let (centre_x, centre_y) = self.centre();
+        self.glyphs = glyphs;
+        self.centre_on(centre_x, centre_y);
+    }
+
+    pub fn colors(&self) -> ColorMode {
+        self.colors
+    }
+
+    /// Sets how cells are coloured, which only applies to text which is not zoomed out.
+    pub fn set_colors(&mut self, colors: ColorMode) {
+        self.colors = colors;
+    }
+
+    /// Limits the view to a terminal of `columns` by `rows` characters.
+    pub fn fit(&mut self, columns: i64, rows: i64) {
+        self.limit = Some((columns, rows));
+        self.clamp();
+    }
+
+    /// Moves the view by `dx` and `dy` characters, each of which covers several cells when zoomed out or drawn with
+    /// glyphs other than text.
+    ///
+    /// # Example
+    /// ```
+    /// use game_of_life::{Glyphs, Region, Viewport};
+    ///
+    /// let mut viewport = Viewport::new(Region { x: 0, y: 0, width: 8, height: 8 }, false);
+    /// viewport.set_glyphs(Glyphs::Braille);
+    /// viewport.pan(1, -1);
+    /// assert_eq!((viewport.region().x, viewport.region().y), (2, -4));
+    /// ```
+    pub fn pan(&mut self, dx: i64, dy: i64) {
+        let (cell_width, cell_height) = self.character_size();
+        self.x += dx * cell_width;
+        self.y += dy * cell_height;
+        self.follow = false;
+        self.clamp();
+    }
+
+    /// Halves the number of cells drawn by each dot, keeping the centre of the view in place.
+    pub fn zoom_in(&mut self) {
+        self.set_scale(self.scale / 2);
+    }
+
+    /// Doubles the number of cells drawn by each dot, keeping the centre of the view in place.
+    pub fn zoom_out(&mut self) {
+        self.set_scale(self.scale * 2);
+    }
+
+    /// Sets the number of cells along each side of the square drawn by each dot.
+    pub fn set_scale(&mut self, scale: i64) {
+        let (centre_x, centre_y) = self.centre();
+        self.scale = scale.clamp(1, Self::MAX_SCALE);
+        self.centre_on(centre_x, centre_y);
+    }
+
+    /// Sets whether the view is kept centred on the pattern by [follow](Viewport::follow).
+    pub fn set_following(&mut self, follow: bool) {
+        self.follow = follow;
+    }
+
+    /// Centres the view on `bounding_box` if the view is following the pattern.
+    pub fn follow(&mut self, bounding_box: Option<Region>) {
+        if let (true, Some(bounding_box)) = (self.follow, bounding_box) {
+            self.centre_on(
+                bounding_box.x + bounding_box.width / 2,
+                bounding_box.y + bounding_box.height / 2,
+            );
+        }
+    }
+
+    /// Returns the rows of the view from top to bottom, in the same format as [frame_lines] when drawing text which
+    /// is not zoomed out.
+    pub fn lines<U: Universe + ?Sized>(&self, universe: &U) -> Vec<String> {
+        let region = self.region();
+        if self.glyphs == Glyphs::Text && self.scale == 1 {
+            return match self.colors {
+                ColorMode::Off => frame_lines(universe, region),
+                colors => colored_frame_lines(universe, region, colors),
+            };
+        }
+
+        let mut counts: HashMap<(i64, i64), i64> = HashMap::new();
+        for (x, y) in universe.live_cells() {
+            if region.contains(x, y) {
+                let dot = ((x - region.x) / self.scale, (y - region.y) / self.scale);
+                *counts.entry(dot).or_default() += 1;
+            }
+        }
+        let alive = |x, y| counts.contains_key(&(x, y));
+
+        let (columns, rows) = self.size();
+        let area = self.scale * self.scale;
+        let separator = if self.glyphs == Glyphs::Text { " " } else { "" };
+        (0..rows)
+            .rev()
+            .map(|row| {
+                (0..columns)
+                    .map(|column| match self.glyphs {
+                        Glyphs::Text => {
+                            let count = counts.get(&(column, row)).copied().unwrap_or_default();
+                            SHADES[((count * 4 + area - 1) / area) as usize]
+                        }
+                        Glyphs::HalfBlock => {
+                            match (alive(column, row * 2 + 1), alive(column, row * 2)) {
+                                (false, false) => ' ',
+                                (true, false) => '▀',
+                                (false, true) => '▄',
+                                (true, true) => '█',
+                            }
+                        }
+                        Glyphs::Braille => {
+                            let mut code = BRAILLE_BLANK;
+                            for (dot_row, bits) in BRAILLE_BITS.iter().enumerate() {
+                                for (dot_column, bit) in bits.iter().enumerate() {
+                                    let x = column * 2 + dot_column as i64;
+                                    let y = row * 4 + 3 - dot_row as i64;
+                                    if alive(x, y) {
+                                        code |= 1 << bit;
+                                    }
+                                }
+                            }
+                            char::from_u32(code).unwrap()
+                        }
+                    })
+                    .map(String::from)
+                    .collect::<Vec<_>>()
+                    .join(separator)
+            })
+            .collect()
+    }
+
+    fn centre(&self) -> (i64, i64) {
+        let region = self.region();
+        (region.x + region.width / 2, region.y + region.height / 2)
+    }
+
+    fn centre_on(&mut self, x: i64, y: i64) {
+        let region = self.region();
+        self.x = x - region.width / 2;
+        self.y = y - region.height / 2;
+        self.clamp();
+    }
+
+    /// Keeps the view within the edges of a fixed-size grid, preferring its bottom-left corner if it is too small.
+    fn clamp(&mut self) {
+        if let Some(bounds) = self.bounds {
+            let region = self.region();
+            self.x = self
+                .x
+                .min(bounds.x + bounds.width - region.width)
+                .max(bounds.x);
+            self.y = self
+                .y
+                .min(bounds.y + bounds.height - region.height)
+                .max(bounds.y);
+        }
+    }
+}